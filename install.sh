@@ -12,11 +12,6 @@ cp target/release/threeaday ~/.cargo/bin/
 cp target/release/threeaday-service ~/.cargo/bin/
 cp target/release/threeaday-gui ~/.cargo/bin/
 
-# Install waybar module
-mkdir -p ~/.config/threeaday
-cp waybar-module.sh ~/.config/threeaday/
-chmod +x ~/.config/threeaday/waybar-module.sh
-
 # Install systemd user service
 mkdir -p ~/.config/systemd/user
 cp threeaday.service ~/.config/systemd/user/
@@ -40,15 +35,14 @@ echo "  systemctl --user stop threeaday     # Stop service"
 echo "  systemctl --user status threeaday   # Check service status"
 echo ""
 echo "Waybar integration:"
-echo "  Module script installed to: ~/.config/threeaday/waybar-module.sh"
 echo "  Add this to your waybar config:"
 echo "    \"threeaday\": {"
 echo "      \"format\": \"{}\","
 echo "      \"return-type\": \"json\","
-echo "      \"exec\": \"~/.config/threeaday/waybar-module.sh status\","
-echo "      \"on-click\": \"~/.config/threeaday/waybar-module.sh click left\","
-echo "      \"on-click-right\": \"~/.config/threeaday/waybar-module.sh click right\","
-echo "      \"on-click-middle\": \"~/.config/threeaday/waybar-module.sh click middle\","
+echo "      \"exec\": \"~/.cargo/bin/threeaday waybar\","
+echo "      \"on-click\": \"~/.cargo/bin/threeaday waybar click left\","
+echo "      \"on-click-right\": \"~/.cargo/bin/threeaday waybar click right\","
+echo "      \"on-click-middle\": \"~/.cargo/bin/threeaday waybar click middle\","
 echo "      \"interval\": 30,"
 echo "      \"tooltip\": true"
 echo "    }"
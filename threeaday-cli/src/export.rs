@@ -0,0 +1,87 @@
+use crate::output::table;
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+use threeaday_core::{DaySummary, NaiveDate, Result, Task};
+
+/// File formats the task history can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// An array of task objects
+    Json,
+    /// A header line plus one line per task
+    Csv,
+    /// A checklist per day, for pasting into notes
+    Markdown,
+}
+
+/// Render the tasks from `from` to `to`, ordered by day, as a whole document
+pub fn render(
+    format: ExportFormat,
+    tasks: &[Task],
+    summaries: &[DaySummary],
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<String> {
+    Ok(match format {
+        ExportFormat::Json => serde_json::to_string_pretty(tasks)? + "\n",
+        ExportFormat::Csv => render_csv(tasks)?,
+        ExportFormat::Markdown => render_markdown(tasks, summaries, from, to),
+    })
+}
+
+fn render_csv(tasks: &[Task]) -> Result<String> {
+    let rows = serde_json::to_value(tasks)?;
+    let rows = rows.as_array().map(Vec::as_slice).unwrap_or_default();
+
+    let mut csv = String::new();
+    for cells in table(rows) {
+        let cells: Vec<String> = cells.iter().map(|cell| csv_escape(cell)).collect();
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+/// Quote a cell if it contains a separator, quote or line break
+fn csv_escape(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn render_markdown(tasks: &[Task], summaries: &[DaySummary], from: NaiveDate, to: NaiveDate) -> String {
+    let summaries: BTreeMap<NaiveDate, &DaySummary> = summaries.iter().map(|s| (s.day, s)).collect();
+
+    let mut markdown = if from == to {
+        format!("# ThreeADay {}\n", from)
+    } else {
+        format!("# ThreeADay {} to {}\n", from, to)
+    };
+    if tasks.is_empty() {
+        markdown.push_str("\nNo tasks.\n");
+    }
+
+    for day_tasks in tasks.chunk_by(|a, b| a.day == b.day) {
+        let day = day_tasks[0].day;
+        let completed = day_tasks.iter().filter(|task| task.completed).count();
+        markdown.push_str(&format!("\n## {}\n\n", day.format("%A %Y-%m-%d")));
+
+        for task in day_tasks {
+            let check = if task.completed { "x" } else { " " };
+            markdown.push_str(&format!("- [{}] {}\n", check, task.text));
+        }
+
+        let mut totals = format!("\n{} of {} done", completed, day_tasks.len());
+        if let Some(summary) = summaries.get(&day) {
+            totals.push_str(&format!(", goal {}", summary.goal));
+            if summary.is_goal_achieved() {
+                totals.push_str(" 🎯");
+            }
+        }
+        markdown.push_str(&totals);
+        markdown.push('\n');
+    }
+    markdown
+}
@@ -1,10 +1,27 @@
-use clap::{Parser, Subcommand};
+mod export;
+mod output;
+mod waybar;
+
+use chrono::{DateTime, Utc};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use export::ExportFormat;
+use output::{print_structured, OutputFormat, StatusOutput, TaskNotFound, EXIT_NOT_FOUND};
+use serde_json::json;
+use std::path::PathBuf;
 use std::process;
-use threeaday_core::{Database, Config, Result, utils::*};
+use threeaday_core::ipc::{self, Request, Response, ServiceStatus};
+use threeaday_core::db::DB_PATH_ENV;
+use threeaday_core::{Database, Config, Context, Event, EventKind, NaiveDate, ReminderPause, Result, Task, Zone, utils::*};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// Output format (`waybar` always prints waybar JSON)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Use the task database at this path (default: $THREEADAY_DB or the data directory)
+    #[arg(long, global = true, value_name = "PATH")]
+    db: Option<PathBuf>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -13,16 +30,79 @@ struct Cli {
 enum Commands {
     /// Add a new task
     Add { text: String },
-    /// List today's tasks
-    List,
+    /// List today's tasks, or those of past days
+    List {
+        /// Show the tasks of a specific day (YYYY-MM-DD)
+        #[arg(long, conflicts_with_all = ["yesterday", "since"])]
+        date: Option<NaiveDate>,
+        /// Show yesterday's tasks
+        #[arg(long, conflicts_with = "since")]
+        yesterday: bool,
+        /// Show all tasks from a day until today, e.g. 7d, 2w or 2026-10-01
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Show what happened to tasks today, or on past days
+    Log {
+        /// Show the changes made on a specific day (YYYY-MM-DD)
+        #[arg(long, conflicts_with_all = ["yesterday", "since"])]
+        date: Option<NaiveDate>,
+        /// Show yesterday's changes
+        #[arg(long, conflicts_with = "since")]
+        yesterday: bool,
+        /// Show all changes from a day until today, e.g. 7d, 2w or 2026-10-01
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Export all tasks, or those since a day, to a file or stdout
+    Export {
+        /// File format to export to
+        #[arg(id = "export_format", long = "format", alias = "to", value_name = "FORMAT", value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// Export the tasks from a day until today, e.g. 7d, 2w or 2026-10-01
+        #[arg(long)]
+        since: Option<String>,
+        /// Write the export to this file instead of stdout
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
     /// Mark a task as completed
     Done { id: i64 },
+    /// Mark a completed task as not done
+    #[command(alias = "reopen")]
+    Undo { id: i64 },
+    /// Change the text of a task
+    Edit { id: i64, text: String },
+    /// Delete a task
+    Rm { id: i64 },
+    /// Move an open task to another day (YYYY-MM-DD)
+    #[command(alias = "move")]
+    Reschedule { id: i64, date: NaiveDate },
     /// Show today's progress
     Status,
+    /// Show your current and longest streak
+    Streak,
+    /// Pause reminders for a while, e.g. 30m or 2h, or "off" to resume them
+    Snooze { duration: String },
+    /// Turn do-not-disturb on or off, or show whether reminders are paused
+    Dnd {
+        #[arg(value_enum)]
+        state: Option<Toggle>,
+    },
+    /// Ask the running service to send a reminder now
+    Remind,
     /// Launch the GUI
     Gui,
+    /// Print status as waybar custom-module JSON
+    Waybar {
+        #[command(subcommand)]
+        action: Option<WaybarAction>,
+    },
     /// Show config file location
-    Config,
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
     /// Start the background service
     StartService,
     /// Stop the background service
@@ -31,70 +111,349 @@ enum Commands {
     ServiceStatus,
 }
 
+#[derive(Subcommand)]
+enum WaybarAction {
+    /// Handle a click on the waybar module
+    Click {
+        #[arg(value_enum)]
+        button: waybar::ClickButton,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Check the config file for mistakes
+    Check,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Toggle {
+    On,
+    Off,
+}
+
+impl Cli {
+    /// Parse the command line. The output format may also follow the
+    /// subcommand, except after `export`: there `--format` is the file
+    /// format, and clap allows only one `--format` per subcommand.
+    fn parse_args() -> Self {
+        let format = Self::command()
+            .get_arguments()
+            .find(|arg| arg.get_id() == "format")
+            .cloned()
+            .expect("Cli has a --format argument");
+        let command = Self::command().mut_subcommands(|subcommand| {
+            if subcommand.get_name() == "export" {
+                subcommand
+            } else {
+                subcommand.arg(format.clone().global(true))
+            }
+        });
+        Self::from_arg_matches(&command.get_matches()).unwrap_or_else(|e| e.exit())
+    }
+}
+
 fn main() {
-    let cli = Cli::parse();
+    let cli = Cli::parse_args();
+    let format = cli.format;
+    
+    // Through the environment, the override also reaches the GUI started
+    // from here
+    if let Some(path) = &cli.db {
+        std::env::set_var(DB_PATH_ENV, path);
+    }
     
     if let Err(e) = run(cli) {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+        let code = if e.is::<TaskNotFound>() { EXIT_NOT_FOUND } else { 1 };
+        if format == OutputFormat::Text {
+            eprintln!("Error: {}", e);
+        } else {
+            eprintln!("{}", json!({ "error": e.to_string() }));
+        }
+        process::exit(code);
     }
 }
 
 fn run(cli: Cli) -> Result<()> {
+    let format = cli.format;
+    let text_output = format == OutputFormat::Text;
+    
     match cli.command {
         Commands::Add { text } => {
             let mut db = Database::new()?;
             let task_id = db.add_task(&text)?;
-            println!("Added task {} with ID {}", text, task_id);
+            if text_output {
+                println!("Added task {} with ID {}", text, task_id);
+            } else {
+                print_structured(format, &db.get_task(task_id)?)?;
+            }
         }
-        Commands::List => {
+        Commands::List { date, yesterday, since } => {
             let db = Database::new()?;
-            let tasks = db.get_today_tasks()?;
+            let today = db.today();
+            let zone = db.clock().now().timezone();
             
-            if tasks.is_empty() {
-                println!("No tasks for today yet. Add some with 'threeaday add \"task text\"'");
+            if let Some(since) = since {
+                let from = parse_since(&since, today)?;
+                let tasks = db.get_tasks_between(from, today)?;
+                
+                if !text_output {
+                    print_structured(format, &tasks)?;
+                } else if tasks.is_empty() {
+                    println!("No tasks since {}", from);
+                } else {
+                    println!("Tasks since {}:", from);
+                    for day_tasks in tasks.chunk_by(|a, b| a.day == b.day) {
+                        println!("{}:", day_tasks[0].day.format("%A %Y-%m-%d"));
+                        print_tasks(day_tasks, &zone);
+                    }
+                }
             } else {
-                println!("Today's tasks:");
-                for task in tasks {
-                    let status = format_task_status(task.completed);
-                    println!("  [{}] {}: {}", status, task.id, task.text);
+                let day = if yesterday {
+                    today.pred_opt().unwrap_or(today)
+                } else {
+                    date.unwrap_or(today)
+                };
+                let tasks = db.get_tasks_for_day(day)?;
+                
+                if !text_output {
+                    print_structured(format, &tasks)?;
+                } else if day == today {
+                    if tasks.is_empty() {
+                        println!("No tasks for today yet. Add some with 'threeaday add \"task text\"'");
+                    } else {
+                        println!("Today's tasks:");
+                        print_tasks(&tasks, &zone);
+                    }
+                } else if tasks.is_empty() {
+                    println!("No tasks for {}", day.format("%A %Y-%m-%d"));
+                } else {
+                    println!("Tasks for {}:", day.format("%A %Y-%m-%d"));
+                    print_tasks(&tasks, &zone);
                 }
             }
         }
+        Commands::Log { date, yesterday, since } => {
+            let db = Database::new()?;
+            let today = db.today();
+            let zone = db.clock().now().timezone();
+            
+            let (from, to, days) = match since {
+                Some(since) => {
+                    let from = parse_since(&since, today)?;
+                    (from, today, format!("since {}", from))
+                }
+                None => {
+                    let day = if yesterday {
+                        today.pred_opt().unwrap_or(today)
+                    } else {
+                        date.unwrap_or(today)
+                    };
+                    let days = if day == today {
+                        "today".to_string()
+                    } else {
+                        format!("on {}", day.format("%A %Y-%m-%d"))
+                    };
+                    (day, day, days)
+                }
+            };
+            let events = db.get_events_between(from, to)?;
+            
+            if !text_output {
+                print_structured(format, &events)?;
+            } else if events.is_empty() {
+                println!("No activity {}", days);
+            } else {
+                println!("Activity {}:", days);
+                for day_events in events.chunk_by(|a, b| a.day == b.day) {
+                    if from != to {
+                        println!("{}:", day_events[0].day.format("%A %Y-%m-%d"));
+                    }
+                    print_events(day_events, &zone);
+                }
+            }
+        }
+        Commands::Export { format: export_format, since, output } => {
+            let db = Database::new()?;
+            let today = db.today();
+            let summaries = db.get_daily_summaries()?;
+            
+            let from = match since {
+                Some(since) => parse_since(&since, today)?,
+                None => summaries.first().map_or(today, |summary| summary.day).min(today),
+            };
+            let tasks = db.get_tasks_between(from, today)?;
+            let document = export::render(export_format, &tasks, &summaries, from, today)?;
+            
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, document)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    if text_output {
+                        println!("Exported {} to {}", format_task_count(tasks.len()), path.display());
+                    } else {
+                        print_structured(format, &json!({ "path": path, "tasks": tasks.len() }))?;
+                    }
+                }
+                None => print!("{}", document),
+            }
+        }
         Commands::Done { id } => {
             let mut db = Database::new()?;
             if db.complete_task(id)? {
+                if !text_output {
+                    print_structured(format, &db.get_task(id)?)?;
+                    return Ok(());
+                }
+                
                 println!("Task {} completed! 🎉", id);
                 
                 // Check if goal is achieved
-                let (completed, _total) = db.get_today_summary()?;
-                if is_daily_goal_achieved(completed) {
-                    println!("🎯 Daily goal achieved! You completed {} tasks today!", completed);
+                let summary = db.get_today_summary()?;
+                if summary.is_goal_achieved() {
+                    println!("🎯 Daily goal achieved! You completed {} today!", format_task_count(summary.completed));
+                }
+            } else {
+                return Err(TaskNotFound(format!("Task {} not found or already completed", id)).into());
+            }
+        }
+        Commands::Undo { id } => {
+            let mut db = Database::new()?;
+            if db.uncomplete_task(id)? {
+                if text_output {
+                    println!("Task {} reopened", id);
+                } else {
+                    print_structured(format, &db.get_task(id)?)?;
                 }
             } else {
-                println!("Task {} not found or already completed", id);
+                return Err(TaskNotFound(format!("Task {} not found or not completed", id)).into());
+            }
+        }
+        Commands::Edit { id, text } => {
+            let mut db = Database::new()?;
+            if db.update_task_text(id, &text)? {
+                if text_output {
+                    println!("Task {} updated: {}", id, text);
+                } else {
+                    print_structured(format, &db.get_task(id)?)?;
+                }
+            } else {
+                return Err(TaskNotFound(format!("Task {} not found", id)).into());
+            }
+        }
+        Commands::Rm { id } => {
+            let mut db = Database::new()?;
+            if db.delete_task(id)? {
+                if text_output {
+                    println!("Task {} deleted", id);
+                } else {
+                    print_structured(format, &json!({ "id": id, "deleted": true }))?;
+                }
+            } else {
+                return Err(TaskNotFound(format!("Task {} not found", id)).into());
+            }
+        }
+        Commands::Reschedule { id, date } => {
+            let mut db = Database::new()?;
+            if db.reschedule_task(id, date)? {
+                if text_output {
+                    println!("Task {} moved to {}", id, date);
+                } else {
+                    print_structured(format, &db.get_task(id)?)?;
+                }
+            } else {
+                return Err(TaskNotFound(format!("Task {} not found, completed or already on {}", id, date)).into());
             }
         }
         Commands::Status => {
             let db = Database::new()?;
-            let (completed, total) = db.get_today_summary()?;
-            println!("Today's progress: {}/{} tasks completed", completed, total);
+            let summary = db.get_today_summary()?;
+            if !text_output {
+                print_structured(format, &StatusOutput::from(summary))?;
+                return Ok(());
+            }
             
-            if is_daily_goal_achieved(completed) {
+            println!("Today's progress: {}/{} tasks completed", summary.completed, summary.total);
+            
+            if summary.is_goal_achieved() {
                 println!("🎯 Daily goal achieved! Great job!");
             } else {
-                let remaining = DAILY_GOAL_COMPLETION_COUNT.saturating_sub(completed);
-                println!("You need {} more task(s) to reach your daily goal of {}", remaining, DAILY_GOAL_COMPLETION_COUNT);
+                println!("You need {} more task(s) to reach your daily goal of {}", summary.remaining(), summary.goal);
+            }
+        }
+        Commands::Streak => {
+            let db = Database::new()?;
+            let streak = db.get_streak()?;
+            
+            if !text_output {
+                print_structured(format, &streak)?;
+            } else if streak.current > 0 {
+                println!("🔥 Current streak: {} day(s)", streak.current);
+            } else {
+                println!("No current streak. Complete your daily goal today to start one!");
+            }
+            if text_output {
+                println!("🏆 Longest streak: {} day(s)", streak.longest);
+            }
+        }
+        Commands::Snooze { duration } => {
+            let mut db = Database::new()?;
+            let snoozed_until = if duration.trim() == "off" {
+                None
+            } else {
+                let until = db.clock().utc_now().checked_add_signed(parse_duration(&duration)?);
+                Some(until.with_context(|| format!("Cannot snooze for {}", duration.trim()))?)
+            };
+            let pause = db.update_reminder_pause(|pause| pause.snoozed_until = snoozed_until)?;
+            
+            if !text_output {
+                print_structured(format, &pause)?;
+            } else if let Some(until) = pause.snoozed_until {
+                println!("Reminders snoozed until {}", format_time(until, &db.clock().now().timezone()));
+            } else {
+                println!("Snooze cancelled, reminders are back on");
+            }
+        }
+        Commands::Dnd { state } => {
+            let mut db = Database::new()?;
+            let pause = match state {
+                Some(state) => db.update_reminder_pause(|pause| pause.dnd = state == Toggle::On)?,
+                None => db.get_reminder_pause()?,
+            };
+            
+            if text_output {
+                print_pause(&pause, db.clock().now());
+            } else {
+                print_structured(format, &pause)?;
             }
         }
         Commands::Gui => {
             // Launch GUI in background
             let output = std::process::Command::new("threeaday-gui")
                 .spawn()?;
-            println!("GUI launched (PID: {})", output.id());
+            if text_output {
+                println!("GUI launched (PID: {})", output.id());
+            } else {
+                print_structured(format, &json!({ "pid": output.id() }))?;
+            }
         }
-        Commands::Config => {
+        Commands::Waybar { action } => match action {
+            None => waybar::print_status()?,
+            Some(WaybarAction::Click { button }) => waybar::handle_click(button)?,
+        },
+        Commands::Config { action: Some(ConfigAction::Check) } => {
+            check_config(format)?;
+        }
+        Commands::Config { action: None } => {
             let config_path = Config::get_config_path()?;
+            if !text_output {
+                print_structured(format, &json!({
+                    "path": config_path,
+                    "exists": config_path.exists(),
+                }))?;
+                return Ok(());
+            }
+            
             println!("Config file location: {}", config_path.display());
             
             if config_path.exists() {
@@ -105,10 +464,12 @@ fn run(cli: Cli) -> Result<()> {
         }
         Commands::StartService => {
             let output = std::process::Command::new("systemctl")
-                .args(&["--user", "start", "threeaday"])
+                .args(["--user", "start", "threeaday"])
                 .output()?;
             
-            if output.status.success() {
+            if !text_output {
+                print_service_result(format, &output)?;
+            } else if output.status.success() {
                 println!("Service started successfully");
             } else {
                 eprintln!("Failed to start service: {}", String::from_utf8_lossy(&output.stderr));
@@ -116,24 +477,156 @@ fn run(cli: Cli) -> Result<()> {
         }
         Commands::StopService => {
             let output = std::process::Command::new("systemctl")
-                .args(&["--user", "stop", "threeaday"])
+                .args(["--user", "stop", "threeaday"])
                 .output()?;
             
-            if output.status.success() {
+            if !text_output {
+                print_service_result(format, &output)?;
+            } else if output.status.success() {
                 println!("Service stopped successfully");
             } else {
                 eprintln!("Failed to stop service: {}", String::from_utf8_lossy(&output.stderr));
             }
         }
+        Commands::Remind => {
+            match ipc::request(&Request::TriggerReminder)? {
+                Response::Error { message } => anyhow::bail!("Service failed to send a reminder: {}", message),
+                _ if text_output => println!("Reminder sent"),
+                _ => print_structured(format, &json!({ "success": true }))?,
+            }
+        }
         Commands::ServiceStatus => {
+            // Ask the running service first, then fall back to systemd's view
+            if let Ok(Response::Status(status)) = ipc::request(&Request::GetStatus) {
+                if text_output {
+                    // Like the other commands, the CLI tells time by the system clock
+                    print_service_status(&status, &Zone::Local);
+                } else {
+                    let mut value = serde_json::to_value(&status)?;
+                    value["active"] = true.into();
+                    print_structured(format, &value)?;
+                }
+                return Ok(());
+            }
+
             let output = std::process::Command::new("systemctl")
-                .args(&["--user", "status", "threeaday"])
+                .args(["--user", "status", "threeaday"])
                 .output()?;
             
-            println!("{}", String::from_utf8_lossy(&output.stdout));
+            if text_output {
+                println!("{}", String::from_utf8_lossy(&output.stdout));
+            } else {
+                print_structured(format, &json!({
+                    "active": output.status.success(),
+                    "output": String::from_utf8_lossy(&output.stdout),
+                }))?;
+            }
         }
     }
     
     Ok(())
 }
 
+fn print_tasks(tasks: &[Task], zone: &Zone) {
+    for task in tasks {
+        let status = format_task_status(task.completed);
+        println!("  [{}] {}: {} ({})", status, task.id, task.text, format_task_times(task, zone));
+    }
+}
+
+fn print_events(events: &[Event], zone: &Zone) {
+    for event in events {
+        let verb = match event.kind {
+            EventKind::Add => "added",
+            EventKind::Complete => "completed",
+            EventKind::Uncomplete => "reopened",
+            EventKind::Edit => "edited",
+            EventKind::Delete => "deleted",
+            EventKind::Reschedule => "moved",
+        };
+        println!(
+            "  {} {} {}: {} (from {})",
+            format_time(event.at, zone),
+            verb,
+            event.task_id,
+            event.text,
+            event.source
+        );
+    }
+}
+
+fn print_pause(pause: &ReminderPause, now: DateTime<Zone>) {
+    if pause.dnd {
+        println!("Do not disturb is on - no reminders until 'threeaday dnd off'");
+    } else {
+        println!("Do not disturb is off");
+    }
+    if let Some(until) = pause.snoozed_until.filter(|&until| until > now) {
+        println!("Reminders snoozed until {}", format_time(until, &now.timezone()));
+    }
+}
+
+/// Report errors and unknown settings in the config file; errors make the
+/// command fail
+fn check_config(format: OutputFormat) -> Result<()> {
+    let config_path = Config::get_config_path()?;
+    let content = match std::fs::read_to_string(&config_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let (error, unknown_keys) = match Config::parse(&content) {
+        Ok((_, unknown_keys)) => (None, unknown_keys),
+        Err(error) => (Some(error), Vec::new()),
+    };
+    
+    if format == OutputFormat::Text {
+        for key in &unknown_keys {
+            println!("Warning: {}: {}", config_path.display(), key);
+        }
+        if error.is_none() {
+            println!("{}: OK", config_path.display());
+        }
+    } else {
+        print_structured(format, &json!({
+            "path": config_path,
+            "valid": error.is_none(),
+            "error": error,
+            "unknown_keys": unknown_keys,
+        }))?;
+    }
+    
+    match error {
+        Some(error) => anyhow::bail!("{}: {}", config_path.display(), error),
+        None => Ok(()),
+    }
+}
+
+fn print_service_status(status: &ServiceStatus, zone: &Zone) {
+    let local_time = |at: DateTime<Utc>| format_time(at, zone);
+
+    println!("Service is running (since {})", status.started_at.with_timezone(zone).format("%Y-%m-%d %H:%M"));
+    println!("Day: {}", status.day);
+    println!("Reminders sent: {}/{}", status.reminders_sent, status.max_reminders);
+    if let Some(at) = status.last_reminder_at {
+        println!("Last reminder: {}", local_time(at));
+    }
+    match status.next_reminder_at {
+        Some(at) => println!("Next reminder: {}", local_time(at)),
+        None => println!("Next reminder: none today"),
+    }
+    if status.paused {
+        println!("Reminders are paused");
+    }
+    match status.last_reset {
+        Some(day) => println!("Last daily reset: {}", day),
+        None => println!("Last daily reset: not yet"),
+    }
+}
+
+fn print_service_result(format: OutputFormat, output: &std::process::Output) -> Result<()> {
+    print_structured(format, &json!({
+        "success": output.status.success(),
+        "error": String::from_utf8_lossy(&output.stderr).trim(),
+    }))
+}
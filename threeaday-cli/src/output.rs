@@ -0,0 +1,110 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+use threeaday_core::{DaySummary, NaiveDate, Result};
+
+/// Exit code used when a command refers to a task that doesn't exist or is
+/// not in the required state
+pub const EXIT_NOT_FOUND: i32 = 3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// One JSON document per command
+    Json,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+#[derive(Debug)]
+pub struct TaskNotFound(pub String);
+
+impl fmt::Display for TaskNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TaskNotFound {}
+
+/// `threeaday status` output
+#[derive(Debug, Serialize)]
+pub struct StatusOutput {
+    pub day: NaiveDate,
+    pub completed: usize,
+    pub total: usize,
+    pub goal: usize,
+    pub remaining: usize,
+    pub achieved: bool,
+}
+
+impl From<DaySummary> for StatusOutput {
+    fn from(summary: DaySummary) -> Self {
+        StatusOutput {
+            day: summary.day,
+            completed: summary.completed,
+            total: summary.total,
+            goal: summary.goal,
+            remaining: summary.remaining(),
+            achieved: summary.is_goal_achieved(),
+        }
+    }
+}
+
+/// Print a value in one of the machine-readable formats
+pub fn print_structured<T: Serialize>(format: OutputFormat, value: &T) -> Result<()> {
+    match format {
+        OutputFormat::Text | OutputFormat::Json => {
+            println!("{}", serde_json::to_string(value)?);
+        }
+        OutputFormat::Tsv => {
+            let rows = match serde_json::to_value(value)? {
+                Value::Array(rows) => rows,
+                row => vec![row],
+            };
+            for line in tsv_lines(&rows) {
+                println!("{}", line);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Lay out rows of JSON objects as a header row of their keys plus one row
+/// of cells each; missing values and nulls are empty cells
+pub fn table(rows: &[Value]) -> Vec<Vec<String>> {
+    let Some(Value::Object(first)) = rows.first() else {
+        return Vec::new();
+    };
+    
+    let columns: Vec<&String> = first.keys().collect();
+    let mut table = vec![columns.iter().map(|c| c.to_string()).collect()];
+    
+    for row in rows {
+        let cells = columns
+            .iter()
+            .map(|column| match row.get(column.as_str()).unwrap_or(&Value::Null) {
+                Value::Null => String::new(),
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect();
+        table.push(cells);
+    }
+    table
+}
+
+/// Render rows of JSON objects as a header line plus one line per row
+fn tsv_lines(rows: &[Value]) -> Vec<String> {
+    table(rows)
+        .iter()
+        .map(|cells| cells.iter().map(|cell| tsv_escape(cell)).collect::<Vec<_>>().join("\t"))
+        .collect()
+}
+
+fn tsv_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n")
+}
@@ -0,0 +1,143 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::process::{Command, Stdio};
+use threeaday_core::{Database, DaySummary, Result, utils::*};
+
+/// Custom-module output understood by waybar's `"return-type": "json"`.
+/// `class` matches the selectors in `waybar-style-example.css`.
+#[derive(Debug, Serialize)]
+pub struct WaybarStatus {
+    pub text: String,
+    pub tooltip: String,
+    pub class: &'static str,
+}
+
+impl WaybarStatus {
+    pub fn from_summary(summary: &DaySummary) -> Self {
+        let completed = summary.completed;
+        let total = summary.total;
+        
+        if summary.total > 0 && summary.is_goal_achieved() {
+            WaybarStatus {
+                text: format!("🎯 {}", completed),
+                tooltip: format!("ThreeADay: Goal achieved! {} completed", format_task_count(completed)),
+                class: "achieved",
+            }
+        } else if total == 0 {
+            WaybarStatus {
+                text: "📝 0".to_string(),
+                tooltip: "ThreeADay: No tasks yet. Click to add some!".to_string(),
+                class: "empty",
+            }
+        } else {
+            let class = match completed {
+                0 => "none",
+                1 => "started",
+                _ => "progress",
+            };
+            WaybarStatus {
+                text: format!("📋 {}/{}", completed, total),
+                tooltip: format!(
+                    "ThreeADay: {}/{} tasks completed. {} more to go!",
+                    completed, total, summary.remaining()
+                ),
+                class,
+            }
+        }
+    }
+    
+    pub fn error(error: &anyhow::Error) -> Self {
+        WaybarStatus {
+            text: "📋 ?".to_string(),
+            tooltip: format!("ThreeADay: {}", error),
+            class: "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ClickButton {
+    /// Open the GUI
+    Left,
+    /// Show today's tasks in a notification
+    Right,
+    /// Quick-add a task via rofi or zenity
+    Middle,
+}
+
+/// Print the module status. Errors are reported in the module itself so
+/// waybar always receives valid JSON.
+pub fn print_status() -> Result<()> {
+    let status = match Database::new().and_then(|db| db.get_today_summary()) {
+        Ok(summary) => WaybarStatus::from_summary(&summary),
+        Err(e) => WaybarStatus::error(&e),
+    };
+    println!("{}", serde_json::to_string(&status)?);
+    Ok(())
+}
+
+pub fn handle_click(button: ClickButton) -> Result<()> {
+    match button {
+        ClickButton::Left => {
+            Command::new("threeaday-gui").spawn()?;
+        }
+        ClickButton::Right => {
+            let db = Database::new()?;
+            let tasks = db.get_today_tasks()?;
+            let body = if tasks.is_empty() {
+                "No tasks for today yet".to_string()
+            } else {
+                tasks
+                    .iter()
+                    .map(|task| format!("[{}] {}: {}", format_task_status(task.completed), task.id, task.text))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            notify_send("ThreeADay Tasks", &body, 5000)?;
+        }
+        ClickButton::Middle => {
+            if let Some(text) = prompt_for_task()? {
+                let mut db = Database::new()?;
+                db.add_task(&text)?;
+                notify_send("ThreeADay", &format!("Added: {}", text), 2000)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Ask for a task with rofi, falling back to zenity
+fn prompt_for_task() -> Result<Option<String>> {
+    let output = if which("rofi") {
+        Command::new("rofi")
+            .args(["-dmenu", "-p", "Add task:", "-lines", "0"])
+            .stdin(Stdio::null())
+            .output()?
+    } else if which("zenity") {
+        Command::new("zenity")
+            .args(["--entry", "--title=ThreeADay", "--text=Add a task:"])
+            .output()?
+    } else {
+        notify_send("ThreeADay", "Middle click: Install rofi or zenity for quick add", 3000)?;
+        return Ok(None);
+    };
+    
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(if output.status.success() && !text.is_empty() { Some(text) } else { None })
+}
+
+fn which(program: &str) -> bool {
+    Command::new("sh")
+        .args(["-c", &format!("command -v {}", program)])
+        .stdout(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn notify_send(summary: &str, body: &str, timeout_ms: u32) -> Result<()> {
+    Command::new("notify-send")
+        .args([summary, body, "-t", &timeout_ms.to_string()])
+        .status()?;
+    Ok(())
+}
@@ -1,24 +1,38 @@
 use assert_cmd::Command;
 use predicates::prelude::*;
-use std::env;
+use std::path::Path;
 use tempfile::TempDir;
 
-fn setup_test_env() -> TempDir {
-    let temp_dir = TempDir::new().unwrap();
-    env::set_var("XDG_DATA_HOME", temp_dir.path());
-    env::set_var("XDG_CONFIG_HOME", temp_dir.path());
-    temp_dir
+/// A private home for one test. Commands get their directories through their
+/// own environment, so tests can run in parallel.
+struct TestEnv {
+    temp_dir: TempDir,
 }
 
-fn threeaday_cmd() -> Command {
-    Command::cargo_bin("threeaday").unwrap()
+impl TestEnv {
+    fn new() -> Self {
+        TestEnv { temp_dir: TempDir::new().unwrap() }
+    }
+    
+    fn path(&self) -> &Path {
+        self.temp_dir.path()
+    }
+    
+    fn cmd(&self) -> Command {
+        let mut cmd = Command::cargo_bin("threeaday").unwrap();
+        cmd.env("XDG_DATA_HOME", self.path())
+            .env("XDG_CONFIG_HOME", self.path())
+            .env("XDG_RUNTIME_DIR", self.path())
+            .env_remove("THREEADAY_DB");
+        cmd
+    }
 }
 
 #[test]
 fn test_add_task_command() {
-    let _temp_dir = setup_test_env();
+    let env = TestEnv::new();
     
-    threeaday_cmd()
+    env.cmd()
         .arg("add")
         .arg("Test task")
         .assert()
@@ -28,9 +42,9 @@ fn test_add_task_command() {
 
 #[test]
 fn test_list_empty_tasks() {
-    let _temp_dir = setup_test_env();
+    let env = TestEnv::new();
     
-    threeaday_cmd()
+    env.cmd()
         .arg("list")
         .assert()
         .success()
@@ -39,17 +53,17 @@ fn test_list_empty_tasks() {
 
 #[test]
 fn test_list_with_tasks() {
-    let _temp_dir = setup_test_env();
+    let env = TestEnv::new();
     
     // Add a task first
-    threeaday_cmd()
+    env.cmd()
         .arg("add")
         .arg("Test task")
         .assert()
         .success();
     
     // List tasks
-    threeaday_cmd()
+    env.cmd()
         .arg("list")
         .assert()
         .success()
@@ -57,19 +71,127 @@ fn test_list_with_tasks() {
         .stdout(predicate::str::contains("Test task"));
 }
 
+#[test]
+fn test_db_override() {
+    let env = TestEnv::new();
+    let db_path = env.path().join("elsewhere").join("tasks.db");
+    
+    env.cmd()
+        .arg("--db")
+        .arg(&db_path)
+        .args(["add", "Elsewhere task"])
+        .assert()
+        .success();
+    
+    env.cmd()
+        .env("THREEADAY_DB", &db_path)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Elsewhere task"));
+    
+    // The option may also follow the subcommand
+    env.cmd()
+        .args(["add", "Second task", "--db"])
+        .arg(&db_path)
+        .assert()
+        .success();
+    env.cmd()
+        .env("THREEADAY_DB", &db_path)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Second task"));
+    
+    // The default database was left alone
+    env.cmd()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks for today yet"));
+}
+
+#[test]
+fn test_list_yesterday_and_date() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("add")
+        .arg("Today task")
+        .assert()
+        .success();
+    
+    env.cmd()
+        .arg("list")
+        .arg("--yesterday")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks for"))
+        .stdout(predicate::str::contains("Today task").not());
+    
+    env.cmd()
+        .arg("list")
+        .arg("--date")
+        .arg("2020-01-01")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks for Wednesday 2020-01-01"));
+}
+
+#[test]
+fn test_list_since() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("add")
+        .arg("Recent task")
+        .assert()
+        .success();
+    
+    env.cmd()
+        .arg("list")
+        .arg("--since")
+        .arg("7d")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tasks since"))
+        .stdout(predicate::str::contains("Recent task"));
+}
+
+#[test]
+fn test_list_invalid_arguments() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("list")
+        .arg("--since")
+        .arg("soon")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid date or span"));
+    
+    env.cmd()
+        .arg("list")
+        .arg("--yesterday")
+        .arg("--since")
+        .arg("7d")
+        .assert()
+        .failure();
+}
+
 #[test]
 fn test_complete_task() {
-    let _temp_dir = setup_test_env();
+    let env = TestEnv::new();
     
     // Add a task first
-    threeaday_cmd()
+    env.cmd()
         .arg("add")
         .arg("Test task")
         .assert()
         .success();
     
     // Complete the task (assuming it gets ID 1)
-    threeaday_cmd()
+    env.cmd()
         .arg("done")
         .arg("1")
         .assert()
@@ -79,79 +201,715 @@ fn test_complete_task() {
 
 #[test]
 fn test_complete_nonexistent_task() {
-    let _temp_dir = setup_test_env();
+    let env = TestEnv::new();
     
-    threeaday_cmd()
+    env.cmd()
         .arg("done")
         .arg("999")
         .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Error: Task 999 not found"));
+}
+
+#[test]
+fn test_undo_task() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("add")
+        .arg("Test task")
+        .assert()
+        .success();
+    
+    env.cmd()
+        .arg("done")
+        .arg("1")
+        .assert()
+        .success();
+    
+    env.cmd()
+        .arg("undo")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 1 reopened"));
+    
+    env.cmd()
+        .arg("status")
+        .assert()
         .success()
-        .stdout(predicate::str::contains("Task 999 not found"));
+        .stdout(predicate::str::contains("0/1 tasks completed"));
+}
+
+#[test]
+fn test_reopen_alias_and_open_task() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("add")
+        .arg("Test task")
+        .assert()
+        .success();
+    
+    env.cmd()
+        .arg("reopen")
+        .arg("1")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Error: Task 1 not found or not completed"));
+}
+
+#[test]
+fn test_edit_task() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("add")
+        .arg("Tkae a walk")
+        .assert()
+        .success();
+    
+    env.cmd()
+        .arg("edit")
+        .arg("1")
+        .arg("Take a walk")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 1 updated: Take a walk"));
+    
+    env.cmd()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Take a walk"))
+        .stdout(predicate::str::contains("Tkae").not());
+}
+
+#[test]
+fn test_rm_task() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("add")
+        .arg("Unwanted task")
+        .assert()
+        .success();
+    
+    env.cmd()
+        .arg("rm")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 1 deleted"));
+    
+    env.cmd()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tasks for today yet"));
+    
+    env.cmd()
+        .arg("rm")
+        .arg("1")
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Error: Task 1 not found"));
+}
+
+#[test]
+fn test_reschedule_task() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("add")
+        .arg("Call the dentist")
+        .assert()
+        .success();
+    
+    env.cmd()
+        .args(["reschedule", "1", "2099-01-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 1 moved to 2099-01-01"));
+    
+    env.cmd()
+        .args(["list", "--date", "2099-01-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Call the dentist"));
+    
+    env.cmd()
+        .args(["move", "1", "2099-01-01"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("Error: Task 1 not found, completed or already on 2099-01-01"));
+    
+    env.cmd()
+        .arg("log")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("moved 1: Call the dentist"));
 }
 
 #[test]
 fn test_status_command() {
-    let _temp_dir = setup_test_env();
+    let env = TestEnv::new();
     
-    threeaday_cmd()
+    env.cmd()
         .arg("status")
         .assert()
         .success()
         .stdout(predicate::str::contains("Today's progress"));
 }
 
+#[test]
+fn test_streak_command() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("streak")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No current streak"))
+        .stdout(predicate::str::contains("Longest streak: 0 day(s)"));
+    
+    for (id, text) in ["Task 1", "Task 2", "Task 3"].iter().enumerate() {
+        env.cmd().arg("add").arg(text).assert().success();
+        env.cmd().arg("done").arg((id + 1).to_string()).assert().success();
+    }
+    
+    env.cmd()
+        .arg("streak")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Current streak: 1 day(s)"));
+}
+
+#[test]
+fn test_status_uses_configured_goal() {
+    let env = TestEnv::new();
+    
+    let config_dir = env.path().join("threeaday");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(config_dir.join("config.toml"), r#"
+reminder_interval_minutes = 45
+daily_reset_time = "00:00"
+max_reminders_per_day = 8
+daily_goal = 1
+"#).unwrap();
+    
+    env.cmd()
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("daily goal of 1"));
+    
+    env.cmd().arg("add").arg("Only task").assert().success();
+    
+    env.cmd()
+        .arg("done")
+        .arg("1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Daily goal achieved! You completed 1 task today!"));
+}
+
+#[test]
+fn test_config_check() {
+    let env = TestEnv::new();
+    let config_dir = env.path().join("threeaday");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    
+    std::fs::write(config_dir.join("config.toml"), "daily_goal = 2\nreminder_intervall_minutes = 30\n").unwrap();
+    env.cmd()
+        .args(["config", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("line 2: unknown setting 'reminder_intervall_minutes'"))
+        .stdout(predicate::str::contains("config.toml: OK"));
+    
+    std::fs::write(config_dir.join("config.toml"), "daily_goal = 2\ndaily_reset_time = \"6am\"\n").unwrap();
+    env.cmd()
+        .args(["config", "check"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("line 2: daily_reset_time: invalid time '6am'"));
+    
+    let output = env.cmd().args(["--format", "json", "config", "check"]).output().unwrap();
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["valid"], false);
+    assert_eq!(report["error"]["line"], 2);
+}
+
+#[test]
+fn test_invalid_config_falls_back_to_defaults() {
+    let env = TestEnv::new();
+    let config_dir = env.path().join("threeaday");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(config_dir.join("config.toml"), "daily_goal = 0\n").unwrap();
+    
+    env.cmd()
+        .args(["add", "Still works"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Warning: using the default config"));
+    env.cmd()
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0/1 tasks completed"))
+        .stdout(predicate::str::contains("daily goal of 3"));
+    assert!(waybar_json(&env)["text"].is_string());
+}
+
+fn waybar_json(env: &TestEnv) -> serde_json::Value {
+    let output = env.cmd().arg("waybar").output().unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_waybar_output() {
+    let env = TestEnv::new();
+    
+    let json = waybar_json(&env);
+    assert_eq!(json["text"], "📝 0");
+    assert_eq!(json["class"], "empty");
+    
+    for text in ["Task 1", "Task 2", "Task 3"] {
+        env.cmd().arg("add").arg(text).assert().success();
+    }
+    
+    let json = waybar_json(&env);
+    assert_eq!(json["text"], "📋 0/3");
+    assert_eq!(json["class"], "none");
+    assert_eq!(json["tooltip"], "ThreeADay: 0/3 tasks completed. 3 more to go!");
+    
+    env.cmd().arg("done").arg("1").assert().success();
+    assert_eq!(waybar_json(&env)["class"], "started");
+    
+    env.cmd().arg("done").arg("2").assert().success();
+    assert_eq!(waybar_json(&env)["class"], "progress");
+    
+    env.cmd().arg("done").arg("3").assert().success();
+    let json = waybar_json(&env);
+    assert_eq!(json["text"], "🎯 3");
+    assert_eq!(json["class"], "achieved");
+}
+
+#[test]
+fn test_waybar_rejects_unknown_click() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("waybar")
+        .arg("click")
+        .arg("sideways")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_log_command() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("log")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No activity today"));
+    
+    env.cmd().args(["add", "Tkae a walk"]).assert().success();
+    env.cmd().args(["edit", "1", "Take a walk"]).assert().success();
+    env.cmd().args(["done", "1"]).assert().success();
+    env.cmd().args(["rm", "1"]).assert().success();
+    
+    env.cmd()
+        .arg("log")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("added 1: Tkae a walk (from cli)"))
+        .stdout(predicate::str::contains("edited 1: Take a walk"))
+        .stdout(predicate::str::contains("completed 1: Take a walk"))
+        .stdout(predicate::str::contains("deleted 1: Take a walk"));
+    
+    let output = env.cmd().args(["--format", "json", "log", "--since", "7d"]).output().unwrap();
+    let events: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let kinds: Vec<_> = events.as_array().unwrap().iter().map(|e| e["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, vec!["add", "edit", "complete", "delete"]);
+    assert_eq!(events[0]["source"], "cli");
+}
+
+#[test]
+fn test_export_command() {
+    let env = TestEnv::new();
+    
+    env.cmd().args(["add", "Walk, \"outside\""]).assert().success();
+    env.cmd().args(["add", "Read a chapter"]).assert().success();
+    env.cmd().args(["done", "1"]).assert().success();
+    
+    let output = env.cmd().arg("export").output().unwrap();
+    assert!(output.status.success());
+    let tasks: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(tasks.as_array().unwrap().len(), 2);
+    assert_eq!(tasks[0]["text"], "Walk, \"outside\"");
+    assert_eq!(tasks[0]["completed"], true);
+    
+    let output = env.cmd().args(["export", "--format", "csv", "--since", "7d"]).output().unwrap();
+    let csv = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines[0], "id,text,completed,day,created_at,completed_at");
+    assert!(lines[1].starts_with("1,\"Walk, \"\"outside\"\"\",true,"));
+    assert!(lines[2].starts_with("2,Read a chapter,false,"));
+    assert!(lines[2].ends_with(','));
+    
+    env.cmd()
+        .args(["export", "--to", "markdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("# ThreeADay "))
+        .stdout(predicate::str::contains("- [x] Walk, \"outside\"\n- [ ] Read a chapter\n"))
+        .stdout(predicate::str::contains("1 of 2 done, goal 3"));
+    
+    let path = env.path().join("wins.md");
+    env.cmd()
+        .args(["export", "--format", "markdown", "--output"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Exported 2 tasks to"));
+    assert!(std::fs::read_to_string(&path).unwrap().contains("- [x] Walk"));
+}
+
+#[test]
+fn test_export_format_flag() {
+    let env = TestEnv::new();
+    
+    env.cmd().args(["add", "Stretch"]).assert().success();
+    
+    env.cmd()
+        .args(["export", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("id,text,completed,day,created_at,completed_at\n1,Stretch,false,"));
+    
+    // Before the subcommand, --format still means the output format
+    let output = env.cmd().args(["--format", "json", "export"]).output().unwrap();
+    let tasks: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(tasks[0]["text"], "Stretch");
+    
+    // Both flags apply: JSON on stdout, CSV in the file
+    let path = env.path().join("tasks.csv");
+    let output = env.cmd()
+        .args(["--format", "json", "export", "--format", "csv", "-o"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["tasks"], 1);
+    assert!(std::fs::read_to_string(&path).unwrap().starts_with("id,text,completed,day,created_at,completed_at\n1,Stretch,"));
+    
+    // After the subcommand, --format is only the file format
+    let path = env.path().join("tasks.md");
+    env.cmd()
+        .args(["export", "--format", "markdown", "-o"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("Exported 1 task to"));
+    assert!(std::fs::read_to_string(&path).unwrap().starts_with("# ThreeADay "));
+    env.cmd()
+        .args(["export", "--format", "csv", "--format", "json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used multiple times"));
+    
+    env.cmd()
+        .args(["export", "--format", "tsv"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("[possible values: json, csv, markdown]"));
+}
+
+#[test]
+fn test_json_output() {
+    let env = TestEnv::new();
+    
+    let output = env.cmd()
+        .args(["--format", "json", "add", "Json task"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let task: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(task["id"], 1);
+    assert_eq!(task["text"], "Json task");
+    assert_eq!(task["completed"], false);
+    assert!(task["completed_at"].is_null());
+    
+    env.cmd().args(["--format", "json", "done", "1"]).assert().success();
+    
+    let output = env.cmd().args(["--format", "json", "list"]).output().unwrap();
+    let tasks: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(tasks.as_array().unwrap().len(), 1);
+    assert_eq!(tasks[0]["completed"], true);
+    assert!(tasks[0]["completed_at"].is_string());
+    
+    let output = env.cmd().args(["--format", "json", "status"]).output().unwrap();
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(status["completed"], 1);
+    assert_eq!(status["total"], 1);
+    assert_eq!(status["goal"], 3);
+    assert_eq!(status["remaining"], 2);
+    assert_eq!(status["achieved"], false);
+    
+    // The format may also follow the subcommand
+    let output = env.cmd().args(["list", "--format", "json"]).output().unwrap();
+    assert!(output.status.success());
+    let tasks: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(tasks[0]["text"], "Json task");
+}
+
+#[test]
+fn test_concurrent_processes() {
+    let env = TestEnv::new();
+    
+    // Writers completing tasks while waybar-style readers poll, all as
+    // separate processes on a database none of them has created yet
+    std::thread::scope(|scope| {
+        for writer in 0..4 {
+            let env = &env;
+            scope.spawn(move || {
+                for n in 0..8 {
+                    let output = env.cmd()
+                        .args(["--format", "json", "add", &format!("Task {} from writer {}", n, writer)])
+                        .output()
+                        .unwrap();
+                    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+                    let task: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+                    env.cmd().args(["done", &task["id"].to_string()]).assert().success();
+                }
+            });
+        }
+        for _ in 0..2 {
+            let env = &env;
+            scope.spawn(move || {
+                for _ in 0..16 {
+                    // waybar reports database errors, a locked one included,
+                    // in the module and still exits successfully
+                    let status = waybar_json(env);
+                    assert_ne!(status["class"], "error", "{}", status["tooltip"]);
+                }
+            });
+        }
+    });
+    
+    let output = env.cmd().args(["--format", "json", "status"]).output().unwrap();
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(status["completed"], 32);
+    assert_eq!(status["total"], 32);
+}
+
+#[test]
+fn test_tsv_output() {
+    let env = TestEnv::new();
+    
+    env.cmd().arg("add").arg("Tab\tseparated").assert().success();
+    
+    env.cmd()
+        .args(["--format", "tsv", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("id\ttext\tcompleted\tday\tcreated_at\tcompleted_at\n"))
+        .stdout(predicate::str::contains("1\tTab\\tseparated\tfalse\t"));
+}
+
+#[test]
+fn test_json_not_found_exit_code() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .args(["--format", "json", "done", "999"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("\"error\":\"Task 999 not found"));
+    
+    env.cmd()
+        .args(["--format", "json", "rm", "999"])
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn test_snooze_command() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .args(["snooze", "2h"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Reminders snoozed until"));
+    
+    let output = env.cmd().args(["--format", "json", "dnd"]).output().unwrap();
+    let pause: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(pause["snoozed_until"].is_string());
+    assert_eq!(pause["dnd"], false);
+    
+    env.cmd()
+        .args(["snooze", "off"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Snooze cancelled"));
+    
+    env.cmd()
+        .args(["snooze", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid duration"));
+    
+    env.cmd()
+        .args(["snooze", "99999999999h"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Cannot snooze for 99999999999h"));
+}
+
+#[test]
+fn test_dnd_command() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("dnd")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Do not disturb is off"));
+    
+    env.cmd()
+        .args(["dnd", "on"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Do not disturb is on"));
+    
+    let output = env.cmd().args(["--format", "json", "dnd"]).output().unwrap();
+    let pause: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(pause["dnd"], true);
+    assert!(pause["snoozed_until"].is_null());
+    
+    env.cmd()
+        .args(["dnd", "off"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Do not disturb is off"));
+}
+
+/// Answer one request on the service socket under `runtime_dir` with `response`
+fn fake_service(runtime_dir: &Path, response: &'static str) -> std::thread::JoinHandle<String> {
+    use std::io::{BufRead, BufReader, Write};
+    
+    let socket_dir = runtime_dir.join("threeaday");
+    std::fs::create_dir_all(&socket_dir).unwrap();
+    let listener = std::os::unix::net::UnixListener::bind(socket_dir.join("service.sock")).unwrap();
+    
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = String::new();
+        BufReader::new(&stream).read_line(&mut request).unwrap();
+        writeln!(stream, "{}", response).unwrap();
+        request
+    })
+}
+
+#[test]
+fn test_service_status_queries_service() {
+    let env = TestEnv::new();
+    let service = fake_service(env.path(), r#"{"result":"status","started_at":"2026-10-16T05:00:00Z","day":"2026-10-16","reminders_sent":2,"max_reminders":8,"last_reminder_at":"2026-10-16T06:30:00Z","next_reminder_at":null,"last_reset":"2026-10-16","paused":false}"#);
+    
+    let output = env.cmd()
+        .args(["--format", "json", "service-status"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(status["active"], true);
+    assert_eq!(status["reminders_sent"], 2);
+    assert_eq!(status["last_reset"], "2026-10-16");
+    assert!(status["next_reminder_at"].is_null());
+    assert_eq!(service.join().unwrap().trim(), r#"{"method":"GetStatus"}"#);
+}
+
+#[test]
+fn test_remind_command() {
+    let env = TestEnv::new();
+    
+    env.cmd()
+        .arg("remind")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Service is not running"));
+    
+    let service = fake_service(env.path(), r#"{"result":"ok"}"#);
+    env.cmd()
+        .arg("remind")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Reminder sent"));
+    assert_eq!(service.join().unwrap().trim(), r#"{"method":"TriggerReminder"}"#);
+}
+
 #[test]
 fn test_full_workflow() {
-    let _temp_dir = setup_test_env();
+    let env = TestEnv::new();
     
     // Add multiple tasks
-    threeaday_cmd()
+    env.cmd()
         .arg("add")
         .arg("Task 1")
         .assert()
         .success();
         
-    threeaday_cmd()
+    env.cmd()
         .arg("add")
         .arg("Task 2")
         .assert()
         .success();
         
-    threeaday_cmd()
+    env.cmd()
         .arg("add")
         .arg("Task 3")
         .assert()
         .success();
     
     // Check status
-    threeaday_cmd()
+    env.cmd()
         .arg("status")
         .assert()
         .success()
         .stdout(predicate::str::contains("0/3 tasks completed"));
     
     // Complete one task
-    threeaday_cmd()
+    env.cmd()
         .arg("done")
         .arg("1")
         .assert()
         .success();
     
     // Check status again
-    threeaday_cmd()
+    env.cmd()
         .arg("status")
         .assert()
         .success()
         .stdout(predicate::str::contains("1/3 tasks completed"));
         
     // Complete remaining tasks
-    threeaday_cmd()
+    env.cmd()
         .arg("done")
         .arg("2")
         .assert()
         .success();
         
-    threeaday_cmd()
+    env.cmd()
         .arg("done")
         .arg("3")
         .assert()
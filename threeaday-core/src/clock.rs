@@ -0,0 +1,188 @@
+use chrono::{DateTime, Duration, FixedOffset, Local, MappedLocalTime, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{Tz, TzOffset};
+use std::fmt;
+use std::sync::Mutex;
+
+/// Source of the current time, so day rollover and reminder timing can be
+/// tested without waiting for real time to pass
+pub trait Clock: Send + Sync {
+    /// Get the current time in the user's timezone
+    fn now(&self) -> DateTime<Zone>;
+
+    /// Get the current time in UTC, as stored in the database
+    fn utc_now(&self) -> DateTime<Utc> {
+        self.now().with_timezone(&Utc)
+    }
+}
+
+/// The timezone a [`Clock`] tells time in: the system's, or a named one for
+/// tests. Unlike a fixed offset it knows when DST starts and ends, so local
+/// times computed from it are right on the other side of a change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Local,
+    Named(Tz),
+}
+
+/// The UTC offset of a [`Zone`] at some instant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneOffset {
+    Local(FixedOffset),
+    Named(TzOffset),
+}
+
+impl Offset for ZoneOffset {
+    fn fix(&self) -> FixedOffset {
+        match self {
+            ZoneOffset::Local(offset) => *offset,
+            ZoneOffset::Named(offset) => offset.fix(),
+        }
+    }
+}
+
+impl fmt::Display for ZoneOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZoneOffset::Local(offset) => offset.fmt(f),
+            ZoneOffset::Named(offset) => offset.fmt(f),
+        }
+    }
+}
+
+impl TimeZone for Zone {
+    type Offset = ZoneOffset;
+
+    fn from_offset(offset: &ZoneOffset) -> Self {
+        match offset {
+            ZoneOffset::Local(_) => Zone::Local,
+            ZoneOffset::Named(offset) => Zone::Named(Tz::from_offset(offset)),
+        }
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<ZoneOffset> {
+        match self {
+            Zone::Local => Local.offset_from_local_date(local).map(ZoneOffset::Local),
+            Zone::Named(tz) => tz.offset_from_local_date(local).map(ZoneOffset::Named),
+        }
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<ZoneOffset> {
+        match self {
+            Zone::Local => Local.offset_from_local_datetime(local).map(ZoneOffset::Local),
+            Zone::Named(tz) => tz.offset_from_local_datetime(local).map(ZoneOffset::Named),
+        }
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> ZoneOffset {
+        match self {
+            Zone::Local => ZoneOffset::Local(Local.offset_from_utc_date(utc)),
+            Zone::Named(tz) => ZoneOffset::Named(tz.offset_from_utc_date(utc)),
+        }
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ZoneOffset {
+        match self {
+            Zone::Local => ZoneOffset::Local(Local.offset_from_utc_datetime(utc)),
+            Zone::Named(tz) => ZoneOffset::Named(tz.offset_from_utc_datetime(utc)),
+        }
+    }
+}
+
+/// The real wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Zone> {
+        Utc::now().with_timezone(&Zone::Local)
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug)]
+pub struct FakeClock {
+    now: Mutex<DateTime<Zone>>,
+}
+
+impl FakeClock {
+    pub fn new(now: DateTime<Zone>) -> Self {
+        FakeClock { now: Mutex::new(now) }
+    }
+
+    /// Create a clock at a local wall-clock time in UTC, e.g. "2026-10-16 09:00"
+    pub fn at(local: &str) -> Self {
+        Self::in_zone(Tz::UTC, local)
+    }
+
+    /// Create a clock at a local wall-clock time in `tz`; a time that occurs
+    /// twice means the first one
+    pub fn in_zone(tz: Tz, local: &str) -> Self {
+        let naive = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M")
+            .unwrap_or_else(|_| panic!("Invalid fake clock time: {}", local));
+        let now = Zone::Named(tz)
+            .from_local_datetime(&naive)
+            .earliest()
+            .unwrap_or_else(|| panic!("{} doesn't exist in {}", local, tz));
+        Self::new(now)
+    }
+
+    pub fn set(&self, now: DateTime<Zone>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Zone> {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Europe::Berlin;
+
+    #[test]
+    fn test_system_clock_is_current() {
+        let before = Utc::now();
+        let now = SystemClock.utc_now();
+        assert!(now >= before && now <= Utc::now());
+        assert_eq!(SystemClock.now().timezone(), Zone::Local);
+    }
+
+    #[test]
+    fn test_fake_clock_at() {
+        let clock = FakeClock::at("2026-10-16 09:30");
+        assert_eq!(clock.now().naive_local().to_string(), "2026-10-16 09:30:00");
+        assert_eq!(clock.utc_now(), clock.now());
+    }
+
+    #[test]
+    fn test_fake_clock_advance_and_set() {
+        let clock = FakeClock::at("2026-10-16 09:30");
+
+        clock.advance(Duration::hours(15));
+        assert_eq!(clock.now().naive_local().to_string(), "2026-10-17 00:30:00");
+
+        let later = FakeClock::at("2026-12-24 18:00").now();
+        clock.set(later);
+        assert_eq!(clock.now(), later);
+    }
+
+    #[test]
+    fn test_fake_clock_follows_dst() {
+        // Summer time in Berlin ends at 03:00 on 2026-10-25
+        let clock = FakeClock::in_zone(Berlin, "2026-10-24 07:00");
+        assert_eq!(clock.utc_now().naive_utc().to_string(), "2026-10-24 05:00:00");
+
+        clock.advance(Duration::hours(24));
+        assert_eq!(clock.now().naive_local().to_string(), "2026-10-25 06:00:00");
+        assert_eq!(clock.now().timezone(), Zone::Named(Berlin));
+    }
+}
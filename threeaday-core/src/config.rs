@@ -1,51 +1,286 @@
+use crate::day::default_reset_time;
+use crate::quiet_hours::QuietHours;
+use crate::utils::DAILY_GOAL_COMPLETION_COUNT;
 use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 use directories::ProjectDirs;
-use serde::{Deserialize, Serialize};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+/// Service and streak settings. Every setting has a default, so a config
+/// file only needs the ones that differ.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
+    #[serde(deserialize_with = "at_least_one")]
     pub reminder_interval_minutes: u64,
-    pub daily_reset_time: String,
+    /// When a new logical day starts, in local time (HH:MM in the file)
+    #[serde(with = "hhmm")]
+    pub daily_reset_time: NaiveTime,
     pub max_reminders_per_day: u32,
+    /// Days of the week that neither extend nor break a streak
+    pub rest_days: Vec<Weekday>,
+    /// Missed days in a row that are forgiven before a streak is broken
+    pub grace_days: u32,
+    /// Number of completed tasks that achieves the day
+    #[serde(deserialize_with = "at_least_one")]
+    pub daily_goal: usize,
+    /// Ranges of local time in which no reminders are sent
+    pub quiet_hours: Vec<QuietHours>,
+    /// Per-weekday overrides of `daily_goal`
+    #[serde(deserialize_with = "goal_overrides")]
+    pub daily_goal_overrides: HashMap<Weekday, usize>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             reminder_interval_minutes: 45,
-            daily_reset_time: "06:00".to_string(),
+            daily_reset_time: default_reset_time(),
             max_reminders_per_day: 8,
+            rest_days: Vec::new(),
+            grace_days: 1,
+            daily_goal: DAILY_GOAL_COMPLETION_COUNT,
+            quiet_hours: Vec::new(),
+            daily_goal_overrides: HashMap::new(),
         }
     }
 }
 
+/// Times of day written as HH:MM
+pub(crate) mod hhmm {
+    use super::*;
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&time.format("%H:%M"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        NaiveTime::parse_from_str(&text, "%H:%M")
+            .map_err(|_| D::Error::custom(format!("invalid time '{}', use HH:MM in 24-hour format", text)))
+    }
+}
+
+fn at_least_one<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + PartialOrd + From<u8>,
+{
+    let value = T::deserialize(deserializer)?;
+    if value < T::from(1) {
+        return Err(D::Error::custom("must be at least 1"));
+    }
+    Ok(value)
+}
+
+/// A goal in `daily_goal_overrides`, checked like `daily_goal`
+#[derive(Deserialize)]
+struct OverrideGoal(#[serde(deserialize_with = "at_least_one")] usize);
+
+fn goal_overrides<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<Weekday, usize>, D::Error> {
+    let overrides = HashMap::<Weekday, OverrideGoal>::deserialize(deserializer)?;
+    Ok(overrides.into_iter().map(|(day, OverrideGoal(goal))| (day, goal)).collect())
+}
+
+/// A config file that can't be used
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigError {
+    /// The line the problem is on, counting from 1
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// A setting in a config file that isn't recognised, likely misspelt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnknownKey {
+    /// Path of the setting, e.g. `quiet_hours[0].strat`
+    pub key: String,
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: unknown setting '{}'", line, self.key),
+            None => write!(f, "unknown setting '{}'", self.key),
+        }
+    }
+}
+
+/// Write a path the way serde_path_to_error does, e.g. `quiet_hours[0].end`
+fn format_path(path: &serde_ignored::Path) -> String {
+    use serde_ignored::Path;
+    match path {
+        Path::Root => String::new(),
+        Path::Seq { parent, index } => format!("{}[{}]", format_path(parent), index),
+        Path::Map { parent, key } => match format_path(parent) {
+            parent if parent.is_empty() => key.clone(),
+            parent => format!("{}.{}", parent, key),
+        },
+        Path::Some { parent } | Path::NewtypeStruct { parent } | Path::NewtypeVariant { parent } => format_path(parent),
+    }
+}
+
+/// Get the line a byte offset is on, counting from 1
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+/// Split a dotted TOML key like `quiet_hours . "start"` into its parts
+fn key_parts(key: &str) -> impl Iterator<Item = &str> {
+    key.split('.').map(|part| part.trim().trim_matches(|c| c == '"' || c == '\''))
+}
+
+/// Join the parts of a table header into a path like `quiet_hours[1].days`,
+/// indexing the arrays of tables among them by how many were seen so far
+fn table_path<'a>(parts: impl Iterator<Item = &'a str>, arrays: &HashMap<String, usize>) -> String {
+    let mut path = String::new();
+    for part in parts {
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(part);
+        if let Some(count) = arrays.get(&path) {
+            path.push_str(&format!("[{}]", count - 1));
+        }
+    }
+    path
+}
+
+/// Find the line where the setting at a path like `quiet_hours[0].dayz` is
+/// set, following `[table]` and `[[array]]` headers. Keys in inline tables
+/// aren't found.
+fn key_line(content: &str, path: &str) -> Option<usize> {
+    let mut table = String::new();
+    // How many tables of each array of tables have been seen
+    let mut arrays: HashMap<String, usize> = HashMap::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        let found = if let Some(header) = line.strip_prefix("[[") {
+            let mut parts: Vec<_> = key_parts(header.split("]]").next()?).collect();
+            let name = parts.pop()?;
+            let parent = table_path(parts.into_iter(), &arrays);
+            let array = if parent.is_empty() { name.to_string() } else { format!("{}.{}", parent, name) };
+            let count = arrays.entry(array.clone()).or_default();
+            *count += 1;
+            table = format!("{}[{}]", array, *count - 1);
+            table == path || (*count == 1 && array == path)
+        } else if let Some(header) = line.strip_prefix('[') {
+            table = table_path(key_parts(header.split(']').next()?), &arrays);
+            table == path
+        } else if let Some((key, _)) = line.split_once('=').filter(|_| !line.starts_with('#')) {
+            let key = key_parts(key).collect::<Vec<_>>().join(".");
+            if table.is_empty() { key == path } else { format!("{}.{}", table, key) == path }
+        } else {
+            false
+        };
+        if found {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
 impl Config {
+    /// Parse the contents of a config file. Missing settings get their
+    /// defaults; settings that aren't recognised are ignored and returned so
+    /// they can be reported.
+    pub fn parse(content: &str) -> std::result::Result<(Self, Vec<UnknownKey>), ConfigError> {
+        let mut unknown = Vec::new();
+        let mut record_unknown = |path: serde_ignored::Path| {
+            let key = format_path(&path);
+            unknown.push(UnknownKey { line: key_line(content, &key), key });
+        };
+        let deserializer = serde_ignored::Deserializer::new(toml::Deserializer::new(content), &mut record_unknown);
+
+        let config = serde_path_to_error::deserialize(deserializer).map_err(|e| {
+            let path = e.path().to_string();
+            let e = e.into_inner();
+            ConfigError {
+                line: e.span().map(|span| line_of(content, span.start)),
+                message: if path == "." {
+                    e.message().to_string()
+                } else {
+                    format!("{}: {}", path, e.message())
+                },
+            }
+        })?;
+        Ok((config, unknown))
+    }
+
+    /// Load the config file, creating it with the defaults if it is missing
     pub fn load() -> Result<Self> {
-        let config_path = Self::get_config_path()?;
-        
-        if config_path.exists() {
-            let content = fs::read_to_string(&config_path)
-                .with_context(|| format!("Failed to read config from {}", config_path.display()))?;
-            
-            let config: Config = toml::from_str(&content)
-                .with_context(|| format!("Failed to parse config from {}", config_path.display()))?;
+        Self::load_from(&Self::get_config_path()?)
+    }
+    
+    /// Load the config file at `path` like [`Config::load`]
+    pub fn load_from(path: &Path) -> Result<Self> {
+        Self::load_with_warnings_from(path).map(|(config, _)| config)
+    }
+    
+    /// Load the config file like [`Config::load`], along with the settings in
+    /// it that weren't recognised
+    pub fn load_with_warnings() -> Result<(Self, Vec<UnknownKey>)> {
+        Self::load_with_warnings_from(&Self::get_config_path()?)
+    }
+    
+    /// Load the config file at `path` like [`Config::load_with_warnings`]
+    pub fn load_with_warnings_from(path: &Path) -> Result<(Self, Vec<UnknownKey>)> {
+        if path.exists() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config from {}", path.display()))?;
             
-            Ok(config)
+            Self::parse(&content)
+                .with_context(|| format!("Failed to parse config from {}", path.display()))
         } else {
             // Create default config
             let config = Config::default();
-            config.save()?;
-            Ok(config)
+            config.save_to(path)?;
+            Ok((config, Vec::new()))
+        }
+    }
+    
+    /// Load the config file if it exists, otherwise use defaults without
+    /// writing anything to disk
+    pub fn load_or_default() -> Result<Self> {
+        Self::load_or_default_from(&Self::get_config_path()?)
+    }
+    
+    /// Load the config file at `path` like [`Config::load_or_default`]
+    pub fn load_or_default_from(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Self::load_from(path)
+        } else {
+            Ok(Config::default())
         }
     }
     
     pub fn save(&self) -> Result<()> {
-        let config_path = Self::get_config_path()?;
-        
+        self.save_to(&Self::get_config_path()?)
+    }
+    
+    /// Write the config to the file at `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
         // Create parent directory if it doesn't exist
-        if let Some(parent) = config_path.parent() {
+        if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
         }
@@ -53,8 +288,8 @@ impl Config {
         let content = toml::to_string_pretty(self)
             .context("Failed to serialize config to TOML")?;
         
-        fs::write(&config_path, content)
-            .with_context(|| format!("Failed to write config to {}", config_path.display()))?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write config to {}", path.display()))?;
         
         Ok(())
     }
@@ -64,40 +299,190 @@ impl Config {
             .context("Failed to get project directories")?;
         Ok(proj_dirs.config_dir().join("config.toml"))
     }
+    
+    /// Get the daily goal for a day, honoring per-weekday overrides
+    pub fn goal_for(&self, day: NaiveDate) -> usize {
+        self.daily_goal_overrides
+            .get(&day.weekday())
+            .copied()
+            .unwrap_or(self.daily_goal)
+    }
+    
+    /// Get the time a new logical day starts at
+    pub fn reset_time(&self) -> NaiveTime {
+        self.daily_reset_time
+    }
+    
+    /// Check whether a local wall-clock time falls into any quiet hours
+    pub fn is_quiet_at(&self, local: NaiveDateTime) -> bool {
+        self.quiet_hours.iter().any(|quiet| quiet.contains(local))
+    }
+    
+    /// Get when the quiet hours around `local` end, following ranges that
+    /// overlap, or `None` if it isn't quiet at `local`
+    pub fn quiet_until(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut until = None;
+        let mut at = local;
+        // Bounded, since ranges covering the whole week would never end
+        for _ in 0..=self.quiet_hours.len() * 7 {
+            match self.quiet_hours.iter().filter_map(|quiet| quiet.end_after(at)).max() {
+                Some(end) => {
+                    until = Some(end);
+                    at = end;
+                }
+                None => break,
+            }
+        }
+        until
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::env;
     use tempfile::TempDir;
 
-    fn setup_test_env() -> TempDir {
+    /// A config path in a directory that doesn't exist yet
+    fn setup_test_env() -> (TempDir, PathBuf) {
         let temp_dir = TempDir::new().unwrap();
-        env::set_var("XDG_CONFIG_HOME", temp_dir.path());
-        temp_dir
+        let config_path = temp_dir.path().join("threeaday").join("config.toml");
+        (temp_dir, config_path)
+    }
+
+    fn time(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").unwrap()
+    }
+
+    fn hours(start: &str, end: &str) -> QuietHours {
+        QuietHours::new(time(start), time(end))
     }
 
     #[test]
     fn test_default_config() {
         let config = Config::default();
         assert_eq!(config.reminder_interval_minutes, 45);
-        assert_eq!(config.daily_reset_time, "06:00");
+        assert_eq!(config.daily_reset_time, time("06:00"));
         assert_eq!(config.max_reminders_per_day, 8);
+        assert!(config.rest_days.is_empty());
+        assert_eq!(config.grace_days, 1);
+        assert_eq!(config.daily_goal, 3);
+        assert!(config.quiet_hours.is_empty());
+        assert!(config.daily_goal_overrides.is_empty());
+    }
+
+    #[test]
+    fn test_config_goal_for_weekday_overrides() {
+        let config = Config {
+            daily_goal: 5,
+            daily_goal_overrides: HashMap::from([(Weekday::Sun, 1)]),
+            ..Config::default()
+        };
+        
+        // 2026-10-03 is a Saturday, 2026-10-04 a Sunday
+        assert_eq!(config.goal_for(NaiveDate::from_ymd_opt(2026, 10, 3).unwrap()), 5);
+        assert_eq!(config.goal_for(NaiveDate::from_ymd_opt(2026, 10, 4).unwrap()), 1);
+    }
+
+    #[test]
+    fn test_config_goal_overrides_from_toml() {
+        let (_temp_dir, config_path) = setup_test_env();
+        
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        fs::write(&config_path, r#"
+reminder_interval_minutes = 45
+daily_reset_time = "06:00"
+max_reminders_per_day = 8
+daily_goal = 2
+
+[daily_goal_overrides]
+Mon = 4
+saturday = 1
+"#).unwrap();
+        
+        let config = Config::load_from(&config_path).unwrap();
+        assert_eq!(config.daily_goal, 2);
+        assert_eq!(config.daily_goal_overrides.get(&Weekday::Mon), Some(&4));
+        assert_eq!(config.daily_goal_overrides.get(&Weekday::Sat), Some(&1));
+    }
+
+    #[test]
+    fn test_config_quiet_hours_from_toml() {
+        let (_temp_dir, config_path) = setup_test_env();
+        
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        fs::write(&config_path, r#"
+reminder_interval_minutes = 45
+daily_reset_time = "06:00"
+max_reminders_per_day = 8
+
+[[quiet_hours]]
+start = "22:00"
+end = "08:00"
+
+[[quiet_hours]]
+start = "14:00"
+end = "15:00"
+days = ["Tue", "Thu"]
+"#).unwrap();
+        
+        let config = Config::load_from(&config_path).unwrap();
+        assert_eq!(config.quiet_hours.len(), 2);
+        assert_eq!(config.quiet_hours[1].days, vec![Weekday::Tue, Weekday::Thu]);
+        
+        // 2026-10-15 is a Thursday
+        let at = |s| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        assert!(config.is_quiet_at(at("2026-10-15 23:00")));
+        assert!(config.is_quiet_at(at("2026-10-15 14:30")));
+        assert!(!config.is_quiet_at(at("2026-10-16 14:30")));
+        assert!(!config.is_quiet_at(at("2026-10-16 12:00")));
+    }
+
+    #[test]
+    fn test_config_quiet_until_follows_overlapping_ranges() {
+        let config = Config {
+            quiet_hours: vec![
+                hours("22:00", "07:00"),
+                hours("06:30", "08:30"),
+            ],
+            ..Config::default()
+        };
+        let at = |s| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        
+        assert_eq!(config.quiet_until(at("2026-10-16 23:00")), Some(at("2026-10-17 08:30")));
+        assert_eq!(config.quiet_until(at("2026-10-17 08:00")), Some(at("2026-10-17 08:30")));
+        assert_eq!(config.quiet_until(at("2026-10-17 12:00")), None);
+    }
+
+    #[test]
+    fn test_config_without_streak_settings() {
+        let (_temp_dir, config_path) = setup_test_env();
+        
+        // Config files written before streaks existed must keep loading
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        fs::write(&config_path, r#"
+reminder_interval_minutes = 30
+daily_reset_time = "07:00"
+max_reminders_per_day = 4
+"#).unwrap();
+        
+        let config = Config::load_from(&config_path).unwrap();
+        assert_eq!(config.reminder_interval_minutes, 30);
+        assert!(config.rest_days.is_empty());
+        assert_eq!(config.grace_days, 1);
+        assert_eq!(config.daily_goal, 3);
     }
 
     #[test]
     fn test_config_load_creates_default_when_missing() {
-        let _temp_dir = setup_test_env();
+        let (_temp_dir, config_path) = setup_test_env();
         
         // Config file shouldn't exist yet
-        let config_path = Config::get_config_path().unwrap();
         assert!(!config_path.exists());
         
         // Loading should create default config
-        let config = Config::load().unwrap();
+        let config = Config::load_from(&config_path).unwrap();
         assert_eq!(config.reminder_interval_minutes, 45);
-        assert_eq!(config.daily_reset_time, "06:00");
+        assert_eq!(config.daily_reset_time, time("06:00"));
         assert_eq!(config.max_reminders_per_day, 8);
         
         // Config file should now exist
@@ -106,47 +491,60 @@ mod tests {
 
     #[test]
     fn test_config_load_reads_existing_file() {
-        let _temp_dir = setup_test_env();
+        let (_temp_dir, config_path) = setup_test_env();
         
         // Create a custom config
         let custom_config = Config {
             reminder_interval_minutes: 30,
-            daily_reset_time: "07:30".to_string(),
+            daily_reset_time: time("07:30"),
             max_reminders_per_day: 5,
+            ..Config::default()
         };
-        custom_config.save().unwrap();
+        custom_config.save_to(&config_path).unwrap();
         
         // Loading should read the custom values
-        let loaded_config = Config::load().unwrap();
+        let loaded_config = Config::load_from(&config_path).unwrap();
         assert_eq!(loaded_config.reminder_interval_minutes, 30);
-        assert_eq!(loaded_config.daily_reset_time, "07:30");
+        assert_eq!(loaded_config.daily_reset_time, time("07:30"));
         assert_eq!(loaded_config.max_reminders_per_day, 5);
     }
 
     #[test]
     fn test_config_save_and_load_roundtrip() {
-        let _temp_dir = setup_test_env();
+        let (_temp_dir, config_path) = setup_test_env();
         
         let original_config = Config {
             reminder_interval_minutes: 120,
-            daily_reset_time: "05:00".to_string(),
+            daily_reset_time: time("05:00"),
             max_reminders_per_day: 10,
+            rest_days: vec![Weekday::Sat, Weekday::Sun],
+            grace_days: 2,
+            daily_goal: 5,
+            quiet_hours: vec![
+                hours("22:00", "08:00"),
+                QuietHours { days: vec![Weekday::Mon], ..hours("10:00", "11:00") },
+            ],
+            daily_goal_overrides: HashMap::from([(Weekday::Sat, 1), (Weekday::Sun, 1)]),
         };
         
         // Save and reload
-        original_config.save().unwrap();
-        let loaded_config = Config::load().unwrap();
+        original_config.save_to(&config_path).unwrap();
+        let loaded_config = Config::load_from(&config_path).unwrap();
         
         assert_eq!(loaded_config.reminder_interval_minutes, 120);
-        assert_eq!(loaded_config.daily_reset_time, "05:00");
+        assert_eq!(loaded_config.daily_reset_time, time("05:00"));
         assert_eq!(loaded_config.max_reminders_per_day, 10);
+        assert_eq!(loaded_config.rest_days, vec![Weekday::Sat, Weekday::Sun]);
+        assert_eq!(loaded_config.grace_days, 2);
+        assert_eq!(loaded_config.daily_goal, 5);
+        assert_eq!(loaded_config.quiet_hours, original_config.quiet_hours);
+        assert_eq!(loaded_config.daily_goal_overrides.get(&Weekday::Sat), Some(&1));
     }
 
     #[test]
     fn test_config_save_creates_directory() {
-        let _temp_dir = setup_test_env();
+        let (_temp_dir, config_path) = setup_test_env();
         
-        let config_path = Config::get_config_path().unwrap();
         let parent_dir = config_path.parent().unwrap();
         
         // Parent directory shouldn't exist initially
@@ -154,7 +552,7 @@ mod tests {
         
         // Saving should create the directory
         let config = Config::default();
-        config.save().unwrap();
+        config.save_to(&config_path).unwrap();
         
         assert!(parent_dir.exists());
         assert!(config_path.exists());
@@ -162,44 +560,153 @@ mod tests {
 
     #[test]
     fn test_config_load_handles_invalid_toml() {
-        let _temp_dir = setup_test_env();
+        let (_temp_dir, config_path) = setup_test_env();
         
         // Create invalid TOML file
-        let config_path = Config::get_config_path().unwrap();
         fs::create_dir_all(config_path.parent().unwrap()).unwrap();
         fs::write(&config_path, "invalid toml content [[[").unwrap();
         
         // Loading should fail gracefully
-        let result = Config::load();
+        let result = Config::load_from(&config_path);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Failed to parse config"));
     }
 
     #[test]
     fn test_config_get_config_path() {
-        let _temp_dir = setup_test_env();
-        
         let path = Config::get_config_path().unwrap();
         assert!(path.to_string_lossy().contains("threeaday"));
         assert!(path.to_string_lossy().ends_with("config.toml"));
     }
 
+    #[test]
+    fn test_config_load_or_default_does_not_create_file() {
+        let (_temp_dir, config_path) = setup_test_env();
+        
+        let config = Config::load_or_default_from(&config_path).unwrap();
+        assert_eq!(config.daily_reset_time, time("06:00"));
+        assert!(!config_path.exists());
+    }
+
+    #[test]
+    fn test_config_reset_time() {
+        let config = Config {
+            daily_reset_time: time("04:30"),
+            ..Config::default()
+        };
+        assert_eq!(config.reset_time(), NaiveTime::from_hms_opt(4, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_config_invalid_reset_time_is_an_error() {
+        let error = Config::parse("daily_reset_time = \"25:99\"").unwrap_err();
+        
+        assert_eq!(error.line, Some(1));
+        assert_eq!(error.message, "daily_reset_time: invalid time '25:99', use HH:MM in 24-hour format");
+    }
+
+    #[test]
+    fn test_config_ranges() {
+        let error = Config::parse("daily_goal = 3\nreminder_interval_minutes = 0\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: reminder_interval_minutes: must be at least 1");
+        
+        let error = Config::parse("daily_goal = 0").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: daily_goal: must be at least 1");
+        
+        let error = Config::parse("grace_days = -1").unwrap_err();
+        assert_eq!(error.line, Some(1));
+        
+        let error = Config::parse("daily_goal = 3\n\n[daily_goal_overrides]\nSat = 1\nSun = 0\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 5: daily_goal_overrides.Sun: must be at least 1");
+    }
+
+    #[test]
+    fn test_config_quiet_hours_times_are_checked() {
+        let error = Config::parse("daily_goal = 3\n\n[[quiet_hours]]\nstart = \"22:00\"\nend = \"8am\"\n").unwrap_err();
+        
+        assert_eq!(error.line, Some(5));
+        assert!(error.message.starts_with("quiet_hours[0].end: invalid time '8am'"));
+    }
+
+    #[test]
+    fn test_config_syntax_error_line() {
+        let error = Config::parse("daily_goal = 3\nrest_days = [\"Sat\"\n").unwrap_err();
+        
+        assert_eq!(error.line, Some(3));
+    }
+
+    #[test]
+    fn test_config_unknown_keys() {
+        let (config, unknown) = Config::parse(r#"
+daily_goal = 2
+reminder_intervall_minutes = 30
+
+[[quiet_hours]]
+start = "22:00"
+end = "08:00"
+dayz = ["Sat"]
+"#).unwrap();
+        
+        assert_eq!(config.daily_goal, 2);
+        assert_eq!(config.reminder_interval_minutes, 45);
+        assert_eq!(unknown, vec![
+            UnknownKey { key: "reminder_intervall_minutes".to_string(), line: Some(3) },
+            UnknownKey { key: "quiet_hours[0].dayz".to_string(), line: Some(8) },
+        ]);
+    }
+
+    #[test]
+    fn test_config_unknown_keys_in_tables() {
+        let (_, unknown) = Config::parse(r#"
+dayz = ["Mon"]
+
+[[quiet_hours]]
+start = "22:00"
+end = "08:00"
+
+[[quiet_hours]]
+start = "12:00"
+end = "13:00"
+dayz = ["Sat"]
+
+[extra]
+dayz = 1
+"#).unwrap();
+        
+        assert_eq!(unknown, vec![
+            UnknownKey { key: "dayz".to_string(), line: Some(2) },
+            UnknownKey { key: "quiet_hours[1].dayz".to_string(), line: Some(11) },
+            UnknownKey { key: "extra".to_string(), line: Some(13) },
+        ]);
+    }
+
     #[test]
     fn test_config_partial_toml() {
-        let _temp_dir = setup_test_env();
+        let (_temp_dir, config_path) = setup_test_env();
         
         // Create partial TOML with only some fields
-        let config_path = Config::get_config_path().unwrap();
         fs::create_dir_all(config_path.parent().unwrap()).unwrap();
         fs::write(&config_path, r#"
 reminder_interval_minutes = 60
 # missing daily_reset_time and max_reminders_per_day
 "#).unwrap();
         
-        // Loading should handle missing fields with defaults
-        let result = Config::load();
-        // This should fail because TOML deserialization requires all fields
-        // unless we add #[serde(default)] attributes
-        assert!(result.is_err());
+        // Missing settings get their defaults
+        let config = Config::load_from(&config_path).unwrap();
+        assert_eq!(config.reminder_interval_minutes, 60);
+        assert_eq!(config.daily_reset_time, time("06:00"));
+        assert_eq!(config.max_reminders_per_day, 8);
+    }
+
+    #[test]
+    fn test_config_load_with_warnings() {
+        let (_temp_dir, config_path) = setup_test_env();
+        
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        fs::write(&config_path, "daily_gaol = 1\n").unwrap();
+        
+        let (config, unknown) = Config::load_with_warnings_from(&config_path).unwrap();
+        assert_eq!(config.daily_goal, 3);
+        assert_eq!(unknown[0].to_string(), "line 1: unknown setting 'daily_gaol'");
     }
 }
\ No newline at end of file
@@ -0,0 +1,65 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Get the default time at which a new logical day starts (06:00)
+pub fn default_reset_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(6, 0, 0).unwrap()
+}
+
+/// Get the logical day a local wall-clock time belongs to.
+///
+/// A day runs from `reset_time` to `reset_time` the next morning, so with a
+/// 06:00 reset a task added at 01:30 still counts towards the previous day.
+pub fn logical_date(local: NaiveDateTime, reset_time: NaiveTime) -> NaiveDate {
+    let date = local.date();
+    if local.time() < reset_time {
+        date.pred_opt().unwrap_or(date)
+    } else {
+        date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_default_reset_time() {
+        assert_eq!(default_reset_time(), NaiveTime::from_hms_opt(6, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_before_reset_belongs_to_previous_day() {
+        let reset = default_reset_time();
+        assert_eq!(logical_date(at("2026-10-02", "01:30"), reset), date("2026-10-01"));
+        assert_eq!(logical_date(at("2026-10-02", "05:59"), reset), date("2026-10-01"));
+    }
+
+    #[test]
+    fn test_at_and_after_reset_belongs_to_same_day() {
+        let reset = default_reset_time();
+        assert_eq!(logical_date(at("2026-10-02", "06:00"), reset), date("2026-10-02"));
+        assert_eq!(logical_date(at("2026-10-02", "23:59"), reset), date("2026-10-02"));
+    }
+
+    #[test]
+    fn test_midnight_reset_matches_calendar_day() {
+        let reset = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+        assert_eq!(logical_date(at("2026-10-02", "00:00"), reset), date("2026-10-02"));
+        assert_eq!(logical_date(at("2026-10-02", "12:00"), reset), date("2026-10-02"));
+    }
+
+    #[test]
+    fn test_crosses_month_and_year_boundaries() {
+        let reset = default_reset_time();
+        assert_eq!(logical_date(at("2026-11-01", "03:00"), reset), date("2026-10-31"));
+        assert_eq!(logical_date(at("2027-01-01", "03:00"), reset), date("2026-12-31"));
+    }
+}
@@ -1,175 +1,954 @@
+use crate::clock::{Clock, SystemClock};
+use crate::config::Config;
+use crate::day::logical_date;
+use crate::event::{Event, EventSource};
+use crate::service_state::{ReminderPause, ServiceDayState};
+use crate::streak::{calculate_streak, Streak};
+use crate::summary::DaySummary;
 use crate::task::Task;
 use crate::utils::get_data_dir;
-use anyhow::{Context, Result};
-use chrono::Utc;
-use rusqlite::{params, Connection};
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use std::collections::BTreeSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+mod jsonl;
+mod memory;
+mod migrations;
+mod sqlite;
+mod store;
+
+pub use jsonl::JsonLinesStore;
+pub use memory::MemoryStore;
+pub use migrations::SCHEMA_VERSION;
+pub use sqlite::SqliteStore;
+pub use store::{Change, DayTotals, TaskStore};
+
+/// Environment variable that overrides where the task database is kept
+pub const DB_PATH_ENV: &str = "THREEADAY_DB";
+
+/// The user's tasks and the service's bookkeeping, seen through the current
+/// config and clock. Where the data is kept is up to its [`TaskStore`].
 pub struct Database {
-    conn: Connection,
+    store: Box<dyn TaskStore>,
+    config: Config,
+    clock: Arc<dyn Clock>,
+    /// Who task changes are logged as coming from
+    source: EventSource,
 }
 
 impl Database {
+    /// Open the user's database at [`Database::default_path`], with the
+    /// settings from their config file
     pub fn new() -> Result<Self> {
-        let db_path = Self::get_db_path()?;
+        Self::open_configured(&Self::default_path()?)
+    }
+
+    /// Open the database at `path` with the settings from the user's config
+    /// file. A config that can't be loaded is reported on stderr and the
+    /// defaults are used, so one bad setting doesn't stop every command;
+    /// `threeaday config check` says what is wrong.
+    pub fn open_configured(path: &Path) -> Result<Self> {
+        let config = Config::load_or_default().unwrap_or_else(|e| {
+            eprintln!("Warning: using the default config: {:#}", e);
+            Config::default()
+        });
+        Ok(Self::open(path)?.with_config(config))
+    }
+
+    /// Open or create the database at `path`, with the default config and
+    /// the system clock. A path ending in `.jsonl` is a [`JsonLinesStore`]
+    /// text file, anything else an [`SqliteStore`] database.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
         
         // Create parent directory if it doesn't exist
-        if let Some(parent) = db_path.parent() {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
             fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create database directory: {}", parent.display()))?;
         }
         
-        let conn = Connection::open(&db_path)
-            .with_context(|| format!("Failed to open database at {}", db_path.display()))?;
-        
-        let mut db = Database { conn };
-        db.init_tables()?;
-        Ok(db)
+        if path.extension().is_some_and(|extension| extension == "jsonl") {
+            Ok(Self::from_store(JsonLinesStore::open(path)?))
+        } else {
+            Ok(Self::from_store(SqliteStore::open(path)?))
+        }
+    }
+
+    /// Open a new, empty database that only lives as long as this handle
+    pub fn open_in_memory() -> Result<Self> {
+        Ok(Self::from_store(SqliteStore::open_in_memory()?))
+    }
+
+    /// Keep the data in `store`, with the default config and the system clock
+    pub fn from_store(store: impl TaskStore + 'static) -> Self {
+        Database {
+            store: Box::new(store),
+            config: Config::default(),
+            clock: Arc::new(SystemClock),
+            source: EventSource::default(),
+        }
+    }
+
+    /// Take the current time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Use `config` for day boundaries, goals and streak rules
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Log task changes as coming from `source` rather than the CLI
+    pub fn with_source(mut self, source: EventSource) -> Self {
+        self.source = source;
+        self
     }
     
-    fn get_db_path() -> Result<PathBuf> {
-        let data_dir = get_data_dir()?;
-        Ok(data_dir.join("tasks.db"))
+    /// Get where the user's database is kept: `$THREEADAY_DB` if it is set,
+    /// otherwise `tasks.db` in the data directory
+    pub fn default_path() -> Result<PathBuf> {
+        match std::env::var_os(DB_PATH_ENV) {
+            Some(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+            _ => Ok(get_data_dir()?.join("tasks.db")),
+        }
     }
     
-    fn init_tables(&mut self) -> Result<()> {
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS tasks (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                text TEXT NOT NULL,
-                completed BOOLEAN NOT NULL DEFAULT FALSE,
-                created_at DATE NOT NULL
-            )",
-            [],
-        )?;
-        Ok(())
+    /// Get the location of the database file, or `None` if it is in memory
+    pub fn path(&self) -> Option<&Path> {
+        self.store.path()
+    }
+
+    /// Get the config used for day boundaries and streak rules
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Replace the config, e.g. after the file was edited
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
+    /// Log the following task changes as coming from `source`
+    pub fn set_source(&mut self, source: EventSource) {
+        self.source = source;
+    }
+
+    /// Get the clock used for timestamps and the current day
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Get the current logical day, honoring the configured reset time
+    pub fn today(&self) -> NaiveDate {
+        logical_date(self.clock.now().naive_local(), self.config.reset_time())
+    }
+
+    /// Describe a change made now, for the event log
+    fn change(&self) -> Change {
+        Change {
+            at: self.clock.utc_now(),
+            day: self.today(),
+            source: self.source,
+        }
     }
 
     pub fn add_task(&mut self, text: &str) -> Result<i64> {
-        let today = Utc::now().date_naive();
-        let _rows_affected = self.conn.execute(
-            "INSERT INTO tasks (text, created_at) VALUES (?1, ?2)",
-            params![text, today],
-        )?;
-        Ok(self.conn.last_insert_rowid())
+        let change = self.change();
+        // Today's goal is recorded so history is later judged against the
+        // goal that was in effect, even if the config changes
+        self.store.add_task(text, self.config.goal_for(change.day), &change)
     }
 
-    pub fn get_today_tasks(&self) -> Result<Vec<Task>> {
-        let today = Utc::now().date_naive();
-        let mut stmt = self.conn.prepare(
-            "SELECT id, text, completed, created_at FROM tasks WHERE created_at = ?1 ORDER BY id"
-        )?;
-        
-        let task_iter = stmt.query_map(params![today], |row| {
-            Ok(Task::new(
-                row.get(0)?,
-                row.get(1)?,
-                row.get(2)?,
-                row.get(3)?,
-            ))
-        })?;
-        
-        let mut tasks = Vec::new();
-        for task in task_iter {
-            tasks.push(task?);
+    /// Get the goal in effect on a day. Today always uses the current config;
+    /// past days use the goal recorded at the time.
+    pub fn goal_for_day(&self, day: NaiveDate) -> Result<usize> {
+        if day == self.today() {
+            return Ok(self.config.goal_for(day));
         }
-        Ok(tasks)
+        
+        let recorded = self.store.recorded_goal(day)?;
+        Ok(recorded.unwrap_or_else(|| self.config.goal_for(day)))
+    }
+
+    /// Get a single task by ID, unless it has been deleted
+    pub fn get_task(&self, id: i64) -> Result<Option<Task>> {
+        self.store.get_task(id)
+    }
+
+    pub fn get_today_tasks(&self) -> Result<Vec<Task>> {
+        self.get_tasks_for_day(self.today())
+    }
+
+    /// Get the tasks of a single logical day
+    pub fn get_tasks_for_day(&self, day: NaiveDate) -> Result<Vec<Task>> {
+        self.get_tasks_between(day, day)
+    }
+
+    /// Get the tasks of all logical days from `from` to `to`, inclusive,
+    /// ordered by day and then by ID
+    pub fn get_tasks_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Task>> {
+        self.store.tasks_between(from, to)
     }
 
     pub fn complete_task(&mut self, id: i64) -> Result<bool> {
-        let rows_affected = self.conn.execute(
-            "UPDATE tasks SET completed = TRUE WHERE id = ?1 AND completed = FALSE",
-            params![id],
-        )?;
-        Ok(rows_affected > 0)
+        let change = self.change();
+        self.store.complete_task(id, self.config.goal_for(change.day), &change)
+    }
+
+    /// Mark a completed task as open again
+    pub fn uncomplete_task(&mut self, id: i64) -> Result<bool> {
+        self.store.uncomplete_task(id, &self.change())
+    }
+
+    /// Replace the text of a task, e.g. to fix a typo
+    pub fn update_task_text(&mut self, id: i64, text: &str) -> Result<bool> {
+        if text.trim().is_empty() {
+            bail!("Task text cannot be empty");
+        }
+        
+        self.store.update_task_text(id, text, &self.change())
+    }
+
+    /// Move an open task to another logical day, e.g. to put it off until
+    /// tomorrow
+    pub fn reschedule_task(&mut self, id: i64, day: NaiveDate) -> Result<bool> {
+        self.store.reschedule_task(id, day, &self.change())
     }
 
-    pub fn get_today_summary(&self) -> Result<(usize, usize)> {
-        let tasks = self.get_today_tasks()?;
+    /// Soft-delete a task. The row is kept for history but hidden from all
+    /// task queries and can no longer be modified.
+    pub fn delete_task(&mut self, id: i64) -> Result<bool> {
+        self.store.delete_task(id, &self.change())
+    }
+
+    /// Get the logged task changes made on the logical days from `from` to
+    /// `to`, inclusive, in the order they happened
+    pub fn get_events_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Event>> {
+        self.store.events_between(from, to)
+    }
+
+    /// Get a summary of every logical day that has tasks, ordered by day
+    pub fn get_daily_summaries(&self) -> Result<Vec<DaySummary>> {
+        let today = self.today();
+        let summaries = self.store.day_totals()?.into_iter().map(|totals| {
+            let goal = match totals.goal {
+                Some(goal) if totals.day != today => goal,
+                _ => self.config.goal_for(totals.day),
+            };
+            DaySummary {
+                day: totals.day,
+                completed: totals.completed,
+                total: totals.total,
+                goal,
+            }
+        });
+        Ok(summaries.collect())
+    }
+
+    /// Compute the current and longest streak of days with the goal achieved
+    pub fn get_streak(&self) -> Result<Streak> {
+        let today = self.today();
+        let summaries = self.get_daily_summaries()?;
+        
+        let first_day = summaries.first().map_or(today, |summary| summary.day);
+        let achieved_days: BTreeSet<NaiveDate> = summaries
+            .iter()
+            .filter(|summary| summary.is_goal_achieved())
+            .map(|summary| summary.day)
+            .collect();
+        
+        Ok(calculate_streak(&achieved_days, first_day, today, &self.config))
+    }
+
+    /// Get the progress of a logical day against the goal in effect that day
+    pub fn get_day_summary(&self, day: NaiveDate) -> Result<DaySummary> {
+        let tasks = self.get_tasks_for_day(day)?;
         let completed = tasks.iter().filter(|t| t.completed).count();
-        Ok((completed, tasks.len()))
+        Ok(DaySummary {
+            day,
+            completed,
+            total: tasks.len(),
+            goal: self.goal_for_day(day)?,
+        })
+    }
+
+    pub fn get_today_summary(&self) -> Result<DaySummary> {
+        self.get_day_summary(self.today())
+    }
+
+    /// Get what the background service has done on a logical day
+    pub fn get_service_state(&self, day: NaiveDate) -> Result<ServiceDayState> {
+        self.store.get_service_state(day)
+    }
+
+    pub fn save_service_state(&mut self, state: &ServiceDayState) -> Result<()> {
+        self.store.save_service_state(state)
+    }
+
+    /// Get whether reminders are snoozed or in do-not-disturb mode
+    pub fn get_reminder_pause(&self) -> Result<ReminderPause> {
+        self.store.get_reminder_pause()
+    }
+
+    pub fn save_reminder_pause(&mut self, pause: &ReminderPause) -> Result<()> {
+        self.store.save_reminder_pause(pause)
+    }
+
+    /// Change the reminder pause and return the result. Nothing else can
+    /// write in between reading and saving, so a snooze set from the CLI
+    /// doesn't undo do-not-disturb set from elsewhere at the same time.
+    pub fn update_reminder_pause(&mut self, mut update: impl FnMut(&mut ReminderPause)) -> Result<ReminderPause> {
+        self.store.update_reminder_pause(&mut update)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::env;
+    use crate::clock::FakeClock;
+    use crate::event::EventKind;
+    use chrono::{Duration, NaiveTime, Utc};
     use tempfile::TempDir;
 
-    fn setup_test_db() -> (Database, TempDir) {
+    /// Run `test` on a new, empty database in each kind of store
+    fn each_store(test: impl Fn(Database)) {
+        test(Database::from_store(SqliteStore::open_in_memory().unwrap()));
+        test(Database::from_store(MemoryStore::new()));
+        let temp_dir = TempDir::new().unwrap();
+        test(Database::open(temp_dir.path().join("tasks.jsonl")).unwrap());
+    }
+
+    /// Run `test` on each kind of store with a fake clock set to `now`
+    fn each_store_at(now: &str, test: impl Fn(Database, Arc<FakeClock>)) {
+        each_store(|db| {
+            let clock = Arc::new(FakeClock::at(now));
+            test(db.with_clock(clock.clone()), clock);
+        });
+    }
+
+    #[test]
+    fn test_open_path_persists_tasks() {
         let temp_dir = TempDir::new().unwrap();
-        let _data_dir = temp_dir.path().join("threeaday");
+        let path = temp_dir.path().join("nested").join("tasks.db");
         
-        // Set environment variable to use test database
-        env::set_var("XDG_DATA_HOME", temp_dir.path());
+        let mut db = Database::open(&path).unwrap();
+        db.add_task("Survive a reopen").unwrap();
+        assert_eq!(db.path(), Some(path.as_path()));
+        drop(db);
         
-        let db = Database::new().unwrap();
-        (db, temp_dir)
+        let db = Database::open(&path).unwrap();
+        assert_eq!(db.get_today_tasks().unwrap()[0].text, "Survive a reopen");
     }
 
     #[test]
-    fn test_add_task() {
-        let (mut db, _temp_dir) = setup_test_db();
+    fn test_open_jsonl_path_uses_text_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tasks.jsonl");
         
-        let task_id = db.add_task("Test task").unwrap();
-        assert!(task_id > 0);
+        let mut db = Database::open(&path).unwrap();
+        let task_id = db.add_task("Plain text").unwrap();
+        db.complete_task(task_id).unwrap();
         
-        let tasks = db.get_today_tasks().unwrap();
-        assert_eq!(tasks.len(), 1);
-        assert_eq!(tasks[0].text, "Test task");
-        assert!(!tasks[0].completed);
+        assert!(fs::read_to_string(&path).unwrap().contains(r#""text":"Plain text""#));
+        let summary = Database::open(&path).unwrap().get_today_summary().unwrap();
+        assert_eq!((summary.completed, summary.total), (1, 1));
     }
 
     #[test]
-    fn test_complete_task() {
-        let (mut db, _temp_dir) = setup_test_db();
+    fn test_concurrent_writers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tasks.db");
         
-        let task_id = db.add_task("Test task").unwrap();
-        let success = db.complete_task(task_id).unwrap();
-        assert!(success);
+        // Every thread opens its own connection, racing to create the schema
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let mut db = Database::open(&path).unwrap();
+                    for n in 0..25 {
+                        let id = db.add_task(&format!("Task {} from writer {}", n, writer)).unwrap();
+                        db.complete_task(id).unwrap();
+                        db.get_today_summary().unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
         
-        let tasks = db.get_today_tasks().unwrap();
-        assert_eq!(tasks.len(), 1);
-        assert!(tasks[0].completed);
+        let summary = Database::open(&path).unwrap().get_today_summary().unwrap();
+        assert_eq!((summary.completed, summary.total), (100, 100));
     }
 
     #[test]
-    fn test_complete_nonexistent_task() {
-        let (mut db, _temp_dir) = setup_test_db();
+    fn test_in_memory_databases_are_isolated() {
+        let mut first = Database::open_in_memory().unwrap();
+        let second = Database::open_in_memory().unwrap();
+        
+        first.add_task("Only here").unwrap();
         
-        let success = db.complete_task(999).unwrap();
-        assert!(!success);
+        assert_eq!(first.get_today_tasks().unwrap().len(), 1);
+        assert!(second.get_today_tasks().unwrap().is_empty());
+        assert_eq!(first.path(), None);
+    }
+
+    #[test]
+    fn test_add_task() {
+        each_store(|mut db| {
+            let task_id = db.add_task("Test task").unwrap();
+            assert!(task_id > 0);
+            
+            let tasks = db.get_today_tasks().unwrap();
+            assert_eq!(tasks.len(), 1);
+            assert_eq!(tasks[0].text, "Test task");
+            assert!(!tasks[0].completed);
+        });
+    }
+
+    #[test]
+    fn test_get_task() {
+        each_store(|mut db| {
+            let task_id = db.add_task("Find me").unwrap();
+            
+            let task = db.get_task(task_id).unwrap().unwrap();
+            assert_eq!(task.text, "Find me");
+            assert!(db.get_task(999).unwrap().is_none());
+            
+            db.delete_task(task_id).unwrap();
+            assert!(db.get_task(task_id).unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn test_complete_task() {
+        each_store(|mut db| {
+            let task_id = db.add_task("Test task").unwrap();
+            let success = db.complete_task(task_id).unwrap();
+            assert!(success);
+            
+            let tasks = db.get_today_tasks().unwrap();
+            assert_eq!(tasks.len(), 1);
+            assert!(tasks[0].completed);
+        });
+    }
+
+    #[test]
+    fn test_task_timestamps() {
+        each_store(|mut db| {
+            let before = Utc::now();
+            let task_id = db.add_task("Timed task").unwrap();
+            
+            let tasks = db.get_today_tasks().unwrap();
+            assert!(tasks[0].created_at >= before);
+            assert!(tasks[0].completed_at.is_none());
+            
+            db.complete_task(task_id).unwrap();
+            
+            let tasks = db.get_today_tasks().unwrap();
+            let completed_at = tasks[0].completed_at.unwrap();
+            assert!(completed_at >= tasks[0].created_at);
+            assert!(tasks[0].time_to_complete().is_some());
+        });
+    }
+
+    #[test]
+    fn test_complete_task_twice_keeps_first_timestamp() {
+        each_store(|mut db| {
+            let task_id = db.add_task("Test task").unwrap();
+            db.complete_task(task_id).unwrap();
+            let first = db.get_today_tasks().unwrap()[0].completed_at;
+            
+            assert!(!db.complete_task(task_id).unwrap());
+            assert_eq!(db.get_today_tasks().unwrap()[0].completed_at, first);
+        });
+    }
+
+    #[test]
+    fn test_complete_nonexistent_task() {
+        each_store(|mut db| {
+            let success = db.complete_task(999).unwrap();
+            assert!(!success);
+        });
+    }
+
+    #[test]
+    fn test_uncomplete_task() {
+        each_store(|mut db| {
+            let task_id = db.add_task("Mis-clicked task").unwrap();
+            db.complete_task(task_id).unwrap();
+            
+            assert!(db.uncomplete_task(task_id).unwrap());
+            
+            let tasks = db.get_today_tasks().unwrap();
+            assert!(!tasks[0].completed);
+            assert!(tasks[0].completed_at.is_none());
+            
+            // Can be completed again afterwards
+            assert!(db.complete_task(task_id).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_uncomplete_open_task() {
+        each_store(|mut db| {
+            let task_id = db.add_task("Open task").unwrap();
+            
+            assert!(!db.uncomplete_task(task_id).unwrap());
+            assert!(!db.uncomplete_task(999).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_update_task_text() {
+        each_store(|mut db| {
+            let task_id = db.add_task("Tkae a walk").unwrap();
+            
+            assert!(db.update_task_text(task_id, "Take a walk").unwrap());
+            assert_eq!(db.get_today_tasks().unwrap()[0].text, "Take a walk");
+            assert!(!db.update_task_text(999, "Missing").unwrap());
+        });
+    }
+
+    #[test]
+    fn test_update_task_text_to_same_text_logs_nothing() {
+        each_store(|mut db| {
+            let today = db.today();
+            let task_id = db.add_task("Take a walk").unwrap();
+            
+            assert!(db.update_task_text(task_id, "Take a walk").unwrap());
+            let kinds: Vec<_> = db.get_events_between(today, today).unwrap().iter().map(|e| e.kind).collect();
+            assert_eq!(kinds, vec![EventKind::Add]);
+        });
+    }
+
+    #[test]
+    fn test_update_task_text_rejects_empty() {
+        each_store(|mut db| {
+            let task_id = db.add_task("Original").unwrap();
+            
+            assert!(db.update_task_text(task_id, "   ").is_err());
+            assert_eq!(db.get_today_tasks().unwrap()[0].text, "Original");
+        });
+    }
+
+    #[test]
+    fn test_reschedule_task() {
+        each_store(|mut db| {
+            let today = db.today();
+            let tomorrow = today.succ_opt().unwrap();
+            let task_id = db.add_task("Call the dentist").unwrap();
+            
+            assert!(db.reschedule_task(task_id, tomorrow).unwrap());
+            assert!(db.get_today_tasks().unwrap().is_empty());
+            assert_eq!(db.get_tasks_for_day(tomorrow).unwrap()[0].id, task_id);
+            
+            // Already on that day
+            assert!(!db.reschedule_task(task_id, tomorrow).unwrap());
+            assert!(!db.reschedule_task(999, today).unwrap());
+            
+            db.complete_task(task_id).unwrap();
+            assert!(!db.reschedule_task(task_id, today).unwrap());
+            
+            let other_id = db.add_task("Water plants").unwrap();
+            db.delete_task(other_id).unwrap();
+            assert!(!db.reschedule_task(other_id, tomorrow).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_delete_task_hides_it() {
+        each_store(|mut db| {
+            let keep_id = db.add_task("Keep").unwrap();
+            let delete_id = db.add_task("Delete").unwrap();
+            db.complete_task(delete_id).unwrap();
+            
+            assert!(db.delete_task(delete_id).unwrap());
+            
+            let tasks = db.get_today_tasks().unwrap();
+            assert_eq!(tasks.len(), 1);
+            assert_eq!(tasks[0].id, keep_id);
+            let summary = db.get_today_summary().unwrap();
+            assert_eq!((summary.completed, summary.total), (0, 1));
+        });
+    }
+
+    #[test]
+    fn test_deleted_task_cannot_be_modified() {
+        each_store(|mut db| {
+            let task_id = db.add_task("Gone").unwrap();
+            db.delete_task(task_id).unwrap();
+            
+            assert!(!db.delete_task(task_id).unwrap());
+            assert!(!db.complete_task(task_id).unwrap());
+            assert!(!db.update_task_text(task_id, "Back").unwrap());
+        });
+    }
+
+    /// A change made from the CLI on a logical day
+    fn change_on(day: NaiveDate) -> Change {
+        Change { at: Utc::now(), day, source: EventSource::Cli }
+    }
+
+    fn add_task_on(db: &mut Database, text: &str, day: NaiveDate) -> i64 {
+        let goal = db.config.goal_for(day);
+        db.store.add_task(text, goal, &change_on(day)).unwrap()
+    }
+
+    #[test]
+    fn test_get_tasks_for_day() {
+        each_store(|mut db| {
+            let today = db.today();
+            let yesterday = today.pred_opt().unwrap();
+            
+            add_task_on(&mut db, "Yesterday's task", yesterday);
+            db.add_task("Today's task").unwrap();
+            
+            let tasks = db.get_tasks_for_day(yesterday).unwrap();
+            assert_eq!(tasks.len(), 1);
+            assert_eq!(tasks[0].text, "Yesterday's task");
+            assert_eq!(tasks[0].day, yesterday);
+            
+            let tasks = db.get_today_tasks().unwrap();
+            assert_eq!(tasks.len(), 1);
+            assert_eq!(tasks[0].text, "Today's task");
+        });
+    }
+
+    #[test]
+    fn test_get_tasks_between() {
+        each_store(|mut db| {
+            let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+            
+            add_task_on(&mut db, "Day 3", day(3));
+            add_task_on(&mut db, "Day 1", day(1));
+            add_task_on(&mut db, "Day 2", day(2));
+            add_task_on(&mut db, "Day 5", day(5));
+            let deleted_id = add_task_on(&mut db, "Deleted", day(2));
+            db.delete_task(deleted_id).unwrap();
+            
+            let tasks = db.get_tasks_between(day(1), day(3)).unwrap();
+            let texts: Vec<_> = tasks.iter().map(|t| t.text.as_str()).collect();
+            assert_eq!(texts, vec!["Day 1", "Day 2", "Day 3"]);
+            
+            assert!(db.get_tasks_between(day(3), day(1)).unwrap().is_empty());
+        });
+    }
+
+    fn complete_day(db: &mut Database, day: NaiveDate, completed: usize) {
+        let goal = db.config.goal_for(day);
+        for i in 0..completed {
+            let id = add_task_on(db, &format!("Task {}", i), day);
+            db.store.complete_task(id, goal, &change_on(day)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_get_daily_summaries() {
+        each_store(|mut db| {
+            let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+            
+            complete_day(&mut db, day(1), 3);
+            add_task_on(&mut db, "Open task", day(1));
+            add_task_on(&mut db, "Open task", day(2));
+            
+            let summaries = db.get_daily_summaries().unwrap();
+            assert_eq!(summaries.len(), 2);
+            assert_eq!((summaries[0].day, summaries[0].completed, summaries[0].total), (day(1), 3, 4));
+            assert_eq!((summaries[1].day, summaries[1].completed, summaries[1].total), (day(2), 0, 1));
+            assert!(summaries[0].is_goal_achieved());
+            assert!(!summaries[1].is_goal_achieved());
+        });
+    }
+
+    #[test]
+    fn test_today_goal_follows_config() {
+        each_store(|mut db| {
+            db.config.daily_goal = 1;
+            
+            let task_id = db.add_task("Just one thing").unwrap();
+            db.complete_task(task_id).unwrap();
+            
+            let summary = db.get_today_summary().unwrap();
+            assert_eq!(summary.goal, 1);
+            assert!(summary.is_goal_achieved());
+        });
+    }
+
+    #[test]
+    fn test_past_days_use_recorded_goal() {
+        each_store(|mut db| {
+            let past = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+            
+            db.config.daily_goal = 2;
+            complete_day(&mut db, past, 2);
+            
+            // Raising the goal later doesn't rewrite history
+            db.config.daily_goal = 5;
+            let summary = db.get_day_summary(past).unwrap();
+            assert_eq!(summary.goal, 2);
+            assert!(summary.is_goal_achieved());
+            
+            // Days without a recorded goal fall back to the config
+            let other = NaiveDate::from_ymd_opt(2026, 10, 2).unwrap();
+            assert_eq!(db.goal_for_day(other).unwrap(), 5);
+        });
+    }
+
+    #[test]
+    fn test_add_task_records_goal() {
+        each_store(|mut db| {
+            db.config.daily_goal = 4;
+            
+            db.add_task("Task").unwrap();
+            
+            assert_eq!(db.store.recorded_goal(db.today()).unwrap(), Some(4));
+        });
+    }
+
+    #[test]
+    fn test_complete_without_change_keeps_goal() {
+        each_store(|mut db| {
+            db.config.daily_goal = 4;
+            let task_id = db.add_task("Task").unwrap();
+            db.complete_task(task_id).unwrap();
+            
+            db.config.daily_goal = 6;
+            assert!(!db.complete_task(task_id).unwrap());
+            assert!(!db.complete_task(999).unwrap());
+            db.delete_task(task_id).unwrap();
+            assert!(!db.complete_task(task_id).unwrap());
+            
+            assert_eq!(db.store.recorded_goal(db.today()).unwrap(), Some(4));
+        });
+    }
+
+    #[test]
+    fn test_get_streak() {
+        each_store(|mut db| {
+            let today = db.today();
+            let days_ago = |n| today - chrono::Duration::days(n);
+            
+            assert_eq!(db.get_streak().unwrap(), Streak::default());
+            
+            complete_day(&mut db, days_ago(5), 3);
+            complete_day(&mut db, days_ago(4), 3);
+            complete_day(&mut db, days_ago(3), 1);
+            complete_day(&mut db, days_ago(2), 3);
+            complete_day(&mut db, days_ago(1), 4);
+            
+            // The day with a single completed task is forgiven by the default grace day
+            let streak = db.get_streak().unwrap();
+            assert_eq!(streak.current, 4);
+            assert_eq!(streak.longest, 4);
+        });
     }
 
     #[test]
     fn test_get_today_summary() {
-        let (mut db, _temp_dir) = setup_test_db();
-        
-        db.add_task("Task 1").unwrap();
-        let task2_id = db.add_task("Task 2").unwrap();
-        db.add_task("Task 3").unwrap();
+        each_store(|mut db| {
+            db.add_task("Task 1").unwrap();
+            let task2_id = db.add_task("Task 2").unwrap();
+            db.add_task("Task 3").unwrap();
+            
+            db.complete_task(task2_id).unwrap();
+            
+            let summary = db.get_today_summary().unwrap();
+            assert_eq!(summary.completed, 1);
+            assert_eq!(summary.total, 3);
+            assert_eq!(summary.goal, 3);
+            assert_eq!(summary.day, db.today());
+        });
+    }
+
+    #[test]
+    fn test_tasks_use_logical_day() {
+        each_store_at("2026-10-17 02:00", |db, _clock| {
+            let config = Config {
+                daily_reset_time: NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+                ..Config::default()
+            };
+            let mut db = db.with_config(config);
+            
+            let task_id = db.add_task("Late night task").unwrap();
+            
+            let task = db.get_task(task_id).unwrap().unwrap();
+            assert_eq!(task.day, NaiveDate::from_ymd_opt(2026, 10, 16).unwrap());
+            assert_eq!(db.get_today_tasks().unwrap().len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_today_rolls_over_at_reset_time() {
+        each_store_at("2026-10-16 23:30", |mut db, clock| {
+            let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+            
+            db.add_task("Before midnight").unwrap();
+            clock.advance(Duration::hours(2));
+            db.add_task("After midnight").unwrap();
+            assert_eq!(db.today(), day);
+            assert_eq!(db.get_today_tasks().unwrap().len(), 2);
+            
+            // The default reset is at 06:00
+            clock.advance(Duration::hours(4) + Duration::minutes(30));
+            assert_eq!(db.today(), day.succ_opt().unwrap());
+            assert!(db.get_today_tasks().unwrap().is_empty());
+            assert_eq!(db.get_tasks_for_day(day).unwrap().len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_timestamps_come_from_clock() {
+        each_store_at("2026-10-16 09:00", |mut db, clock| {
+            let started = clock.utc_now();
+            
+            let task_id = db.add_task("Timed task").unwrap();
+            clock.advance(Duration::minutes(42));
+            db.complete_task(task_id).unwrap();
+            
+            let task = db.get_task(task_id).unwrap().unwrap();
+            assert_eq!(task.created_at, started);
+            assert_eq!(task.completed_at, Some(started + Duration::minutes(42)));
+            assert_eq!(task.time_to_complete(), Some(Duration::minutes(42)));
+        });
+    }
+
+    #[test]
+    fn test_streak_across_simulated_days() {
+        each_store_at("2026-10-12 09:00", |mut db, clock| {
+            for _ in 0..5 {
+                for i in 0..3 {
+                    let id = db.add_task(&format!("Task {}", i)).unwrap();
+                    db.complete_task(id).unwrap();
+                }
+                clock.advance(Duration::days(1));
+            }
+            
+            assert_eq!(db.get_streak().unwrap().current, 5);
+        });
+    }
+
+    #[test]
+    fn test_service_state_defaults_for_new_day() {
+        each_store(|db| {
+            let today = db.today();
+            
+            assert_eq!(db.get_service_state(today).unwrap(), ServiceDayState::new(today));
+        });
+    }
+
+    #[test]
+    fn test_service_state_roundtrip() {
+        each_store(|mut db| {
+            let today = db.today();
+            
+            let mut state = ServiceDayState::new(today);
+            state.reminders_sent = 3;
+            state.last_reminder_at = Some(Utc::now());
+            state.startup_notified = true;
+            state.reset_notified = true;
+            db.save_service_state(&state).unwrap();
+            
+            assert_eq!(db.get_service_state(today).unwrap(), state);
+            
+            state.achievement_notified = true;
+            state.reminders_sent = 4;
+            db.save_service_state(&state).unwrap();
+            
+            assert_eq!(db.get_service_state(today).unwrap(), state);
+            
+            // Other days are unaffected
+            let tomorrow = today.succ_opt().unwrap();
+            assert_eq!(db.get_service_state(tomorrow).unwrap(), ServiceDayState::new(tomorrow));
+        });
+    }
+
+    #[test]
+    fn test_reminder_pause_roundtrip() {
+        each_store(|mut db| {
+            assert_eq!(db.get_reminder_pause().unwrap(), ReminderPause::default());
+            
+            let mut pause = ReminderPause { snoozed_until: Some(Utc::now()), dnd: false };
+            db.save_reminder_pause(&pause).unwrap();
+            assert_eq!(db.get_reminder_pause().unwrap(), pause);
+            
+            pause.dnd = true;
+            pause.snoozed_until = None;
+            db.save_reminder_pause(&pause).unwrap();
+            assert_eq!(db.get_reminder_pause().unwrap(), pause);
+        });
+    }
+
+    #[test]
+    fn test_update_reminder_pause_keeps_other_setting() {
+        each_store(|mut db| {
+            db.save_reminder_pause(&ReminderPause { snoozed_until: None, dnd: true }).unwrap();
+            
+            let snoozed_until = Utc::now();
+            let pause = db.update_reminder_pause(|pause| pause.snoozed_until = Some(snoozed_until)).unwrap();
+            
+            assert_eq!(pause, ReminderPause { snoozed_until: Some(snoozed_until), dnd: true });
+            assert_eq!(db.get_reminder_pause().unwrap(), pause);
+        });
+    }
+
+    /// Change a task in every way and check what the event log says
+    fn check_event_log(db: Database) {
+        let mut db = db.with_clock(Arc::new(FakeClock::at("2026-10-16 09:00")));
+        let today = db.today();
         
-        db.complete_task(task2_id).unwrap();
+        let task_id = db.add_task("Tkae a walk").unwrap();
+        db.update_task_text(task_id, "Take a walk").unwrap();
+        db.complete_task(task_id).unwrap();
+        // Nothing changes, so nothing is logged
+        db.update_task_text(task_id, "Take a walk").unwrap();
+        db.complete_task(task_id).unwrap();
+        db.set_source(EventSource::Gui);
+        db.uncomplete_task(task_id).unwrap();
+        db.reschedule_task(task_id, today.succ_opt().unwrap()).unwrap();
+        db.reschedule_task(task_id, today.succ_opt().unwrap()).unwrap();
+        db.delete_task(task_id).unwrap();
         
-        let (completed, total) = db.get_today_summary().unwrap();
-        assert_eq!(completed, 1);
-        assert_eq!(total, 3);
+        let events = db.get_events_between(today, today).unwrap();
+        let log: Vec<_> = events.iter().map(|e| (e.task_id, e.kind, e.source, e.text.as_str())).collect();
+        assert_eq!(
+            log,
+            vec![
+                (task_id, EventKind::Add, EventSource::Cli, "Tkae a walk"),
+                (task_id, EventKind::Edit, EventSource::Cli, "Take a walk"),
+                (task_id, EventKind::Complete, EventSource::Cli, "Take a walk"),
+                (task_id, EventKind::Uncomplete, EventSource::Gui, "Take a walk"),
+                (task_id, EventKind::Reschedule, EventSource::Gui, "Take a walk"),
+                (task_id, EventKind::Delete, EventSource::Gui, "Take a walk"),
+            ]
+        );
+        assert!(events.iter().all(|e| e.day == today && e.at == db.clock().utc_now()));
+        assert!(db.get_events_between(today.succ_opt().unwrap(), today.succ_opt().unwrap()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_event_log() {
+        each_store(check_event_log);
     }
 
     #[test]
     fn test_multiple_tasks_ordering() {
-        let (mut db, _temp_dir) = setup_test_db();
-        
-        db.add_task("First task").unwrap();
-        db.add_task("Second task").unwrap();
-        db.add_task("Third task").unwrap();
-        
-        let tasks = db.get_today_tasks().unwrap();
-        assert_eq!(tasks.len(), 3);
-        assert_eq!(tasks[0].text, "First task");
-        assert_eq!(tasks[1].text, "Second task");
-        assert_eq!(tasks[2].text, "Third task");
+        each_store(|mut db| {
+            db.add_task("First task").unwrap();
+            db.add_task("Second task").unwrap();
+            db.add_task("Third task").unwrap();
+            
+            let tasks = db.get_today_tasks().unwrap();
+            assert_eq!(tasks.len(), 3);
+            assert_eq!(tasks[0].text, "First task");
+            assert_eq!(tasks[1].text, "Second task");
+            assert_eq!(tasks[2].text, "Third task");
+        });
     }
 }
\ No newline at end of file
@@ -0,0 +1,324 @@
+use super::memory::{MemoryStore, StoredTask};
+use super::store::{Change, DayTotals, TaskStore};
+use crate::event::Event;
+use crate::service_state::{ReminderPause, ServiceDayState};
+use crate::task::Task;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// One line of a task file
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record {
+    Task(StoredTask),
+    Goal { day: NaiveDate, goal: usize },
+    ServiceState(ServiceDayState),
+    ReminderPause(ReminderPause),
+    Event(Event),
+}
+
+/// Read the records of a task file
+fn parse(content: &str) -> Result<MemoryStore> {
+    let mut data = MemoryStore::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(line).with_context(|| format!("line {}", index + 1))?;
+        match record {
+            Record::Task(stored) => {
+                data.tasks.insert(stored.task.id, stored);
+            }
+            Record::Goal { day, goal } => {
+                data.goals.insert(day, goal);
+            }
+            Record::ServiceState(state) => {
+                data.service_states.insert(state.day, state);
+            }
+            Record::ReminderPause(pause) => data.pause = pause,
+            Record::Event(event) => data.events.push(event),
+        }
+    }
+    Ok(data)
+}
+
+/// Write the records of a task file, tasks first and the event log last
+fn serialize(data: &MemoryStore) -> Result<String> {
+    let records = data.tasks.values().cloned().map(Record::Task)
+        .chain(data.goals.iter().map(|(&day, &goal)| Record::Goal { day, goal }))
+        .chain(data.service_states.values().cloned().map(Record::ServiceState))
+        .chain((data.pause != ReminderPause::default()).then_some(Record::ReminderPause(data.pause)))
+        .chain(data.events.iter().cloned().map(Record::Event));
+
+    let mut content = String::new();
+    for record in records {
+        content.push_str(&serde_json::to_string(&record)?);
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+/// Keeps everything in a text file with one JSON object per line, for people
+/// who want to read, grep or edit their tasks with other tools.
+///
+/// Every call reads the whole file, so changes made by other programs are
+/// picked up right away. Changes take a lock on a `.lock` file next to it and
+/// replace the file in one step, so readers never see half of a write.
+pub struct JsonLinesStore {
+    path: PathBuf,
+}
+
+impl JsonLinesStore {
+    /// Use the task file at `path`, which is created on the first change
+    pub fn open(path: &Path) -> Result<Self> {
+        let store = JsonLinesStore { path: path.to_path_buf() };
+        // Report a broken file right away rather than on first use
+        store.load()?;
+        Ok(store)
+    }
+
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(suffix);
+        self.path.with_file_name(name)
+    }
+
+    fn load(&self) -> Result<MemoryStore> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(MemoryStore::new()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", self.path.display())),
+        };
+        parse(&content).with_context(|| format!("Invalid task file {}", self.path.display()))
+    }
+
+    fn save(&self, data: &MemoryStore) -> Result<()> {
+        let temp_path = self.sibling(".tmp");
+        fs::write(&temp_path, serialize(data)?)
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        fs::rename(&temp_path, &self.path)
+            .with_context(|| format!("Failed to replace {}", self.path.display()))
+    }
+
+    /// Take the lock that changes to the file are made under
+    fn lock(&self) -> Result<File> {
+        let lock_path = self.sibling(".lock");
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+        lock.lock().with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+        Ok(lock)
+    }
+
+    /// Apply `change` to the current contents of the file and save the result
+    fn change<T>(&mut self, change: impl FnOnce(&mut MemoryStore) -> Result<T>) -> Result<T> {
+        // Held until the changed file is in place
+        let _lock = self.lock()?;
+
+        let mut data = self.load()?;
+        let result = change(&mut data)?;
+        self.save(&data)?;
+        Ok(result)
+    }
+
+    /// Apply a change to a task like [`change`](Self::change), but leave the
+    /// file alone if nothing was logged, as then the task didn't change
+    fn change_task<T>(&mut self, change: impl FnOnce(&mut MemoryStore) -> Result<T>) -> Result<T> {
+        let _lock = self.lock()?;
+
+        let mut data = self.load()?;
+        let logged = data.events.len();
+        let result = change(&mut data)?;
+        if data.events.len() > logged {
+            self.save(&data)?;
+        }
+        Ok(result)
+    }
+}
+
+impl TaskStore for JsonLinesStore {
+    fn path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+
+    fn add_task(&mut self, text: &str, goal: usize, change: &Change) -> Result<i64> {
+        self.change(|data| data.add_task(text, goal, change))
+    }
+
+    fn get_task(&self, id: i64) -> Result<Option<Task>> {
+        self.load()?.get_task(id)
+    }
+
+    fn tasks_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Task>> {
+        self.load()?.tasks_between(from, to)
+    }
+
+    fn complete_task(&mut self, id: i64, goal: usize, change: &Change) -> Result<bool> {
+        self.change_task(|data| data.complete_task(id, goal, change))
+    }
+
+    fn uncomplete_task(&mut self, id: i64, change: &Change) -> Result<bool> {
+        self.change_task(|data| data.uncomplete_task(id, change))
+    }
+
+    fn update_task_text(&mut self, id: i64, text: &str, change: &Change) -> Result<bool> {
+        self.change_task(|data| data.update_task_text(id, text, change))
+    }
+
+    fn reschedule_task(&mut self, id: i64, day: NaiveDate, change: &Change) -> Result<bool> {
+        self.change_task(|data| data.reschedule_task(id, day, change))
+    }
+
+    fn delete_task(&mut self, id: i64, change: &Change) -> Result<bool> {
+        self.change_task(|data| data.delete_task(id, change))
+    }
+
+    fn events_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Event>> {
+        self.load()?.events_between(from, to)
+    }
+
+    fn recorded_goal(&self, day: NaiveDate) -> Result<Option<usize>> {
+        self.load()?.recorded_goal(day)
+    }
+
+    fn day_totals(&self) -> Result<Vec<DayTotals>> {
+        self.load()?.day_totals()
+    }
+
+    fn get_service_state(&self, day: NaiveDate) -> Result<ServiceDayState> {
+        self.load()?.get_service_state(day)
+    }
+
+    fn save_service_state(&mut self, state: &ServiceDayState) -> Result<()> {
+        self.change(|data| data.save_service_state(state))
+    }
+
+    fn get_reminder_pause(&self) -> Result<ReminderPause> {
+        self.load()?.get_reminder_pause()
+    }
+
+    fn save_reminder_pause(&mut self, pause: &ReminderPause) -> Result<()> {
+        self.change(|data| data.save_reminder_pause(pause))
+    }
+
+    fn update_reminder_pause(&mut self, update: &mut dyn FnMut(&mut ReminderPause)) -> Result<ReminderPause> {
+        self.change(|data| data.update_reminder_pause(update))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventSource;
+    use chrono::{DateTime, Utc};
+    use tempfile::TempDir;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    /// A change made from the CLI at a UTC time like "2026-10-16T09:00:00Z"
+    fn change(at: &str) -> Change {
+        let at: DateTime<Utc> = at.parse().unwrap();
+        Change { at, day: at.date_naive(), source: EventSource::Cli }
+    }
+
+    #[test]
+    fn test_file_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tasks.jsonl");
+        let mut store = JsonLinesStore::open(&path).unwrap();
+        assert!(!path.exists());
+
+        let id = store.add_task("Take a walk", 3, &change("2026-10-16T09:00:00Z")).unwrap();
+        store.complete_task(id, 3, &change("2026-10-16T09:30:00Z")).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            concat!(
+                r#"{"type":"task","id":1,"text":"Take a walk","completed":true,"day":"2026-10-16","#,
+                r#""created_at":"2026-10-16T09:00:00Z","completed_at":"2026-10-16T09:30:00Z"}"#,
+                "\n",
+                r#"{"type":"goal","day":"2026-10-16","goal":3}"#,
+                "\n",
+                r#"{"type":"event","id":1,"at":"2026-10-16T09:00:00Z","day":"2026-10-16","task_id":1,"#,
+                r#""kind":"add","source":"cli","text":"Take a walk"}"#,
+                "\n",
+                r#"{"type":"event","id":2,"at":"2026-10-16T09:30:00Z","day":"2026-10-16","task_id":1,"#,
+                r#""kind":"complete","source":"cli","text":"Take a walk"}"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_unchanged_task_leaves_file_alone() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tasks.jsonl");
+        let mut store = JsonLinesStore::open(&path).unwrap();
+        let id = store.add_task("Take a walk", 3, &change("2026-10-16T09:00:00Z")).unwrap();
+        let inode = fs::metadata(&path).unwrap().ino();
+
+        // Every save replaces the file, so the same file means no save
+        assert!(store.update_task_text(id, "Take a walk", &change("2026-10-16T09:30:00Z")).unwrap());
+        assert!(!store.uncomplete_task(id, &change("2026-10-16T09:30:00Z")).unwrap());
+        assert_eq!(fs::metadata(&path).unwrap().ino(), inode);
+
+        store.update_task_text(id, "Take a long walk", &change("2026-10-16T09:30:00Z")).unwrap();
+        assert_ne!(fs::metadata(&path).unwrap().ino(), inode);
+    }
+
+    #[test]
+    fn test_reads_changes_from_other_handles() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tasks.jsonl");
+        let mut first = JsonLinesStore::open(&path).unwrap();
+        let mut second = JsonLinesStore::open(&path).unwrap();
+
+        first.add_task("From the CLI", 3, &change("2026-10-16T09:00:00Z")).unwrap();
+        second.add_task("From the GUI", 3, &change("2026-10-16T09:01:00Z")).unwrap();
+
+        let texts: Vec<_> = first.tasks_between(day(16), day(16)).unwrap().into_iter().map(|t| t.text).collect();
+        assert_eq!(texts, vec!["From the CLI", "From the GUI"]);
+    }
+
+    #[test]
+    fn test_hand_written_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tasks.jsonl");
+        fs::write(
+            &path,
+            concat!(
+                r#"{"type":"task","id":7,"text":"Water plants","completed":false,"day":"2026-10-15","created_at":"2026-10-15T08:00:00Z","completed_at":null}"#,
+                "\n\n",
+                r#"{"type":"reminder_pause","snoozed_until":null,"dnd":true}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let mut store = JsonLinesStore::open(&path).unwrap();
+        assert_eq!(store.get_task(7).unwrap().unwrap().text, "Water plants");
+        assert!(store.get_reminder_pause().unwrap().dnd);
+        assert_eq!(store.add_task("Next", 3, &change("2026-10-16T09:00:00Z")).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_invalid_line_is_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("tasks.jsonl");
+        fs::write(&path, "{\"type\":\"goal\",\"day\":\"2026-10-16\",\"goal\":3}\n{\"type\":\"task\"\n").unwrap();
+
+        let error = JsonLinesStore::open(&path).err().unwrap();
+        assert!(format!("{:#}", error).contains("line 2"));
+    }
+}
@@ -0,0 +1,192 @@
+use super::store::{Change, DayTotals, TaskStore};
+use crate::event::{Event, EventKind};
+use crate::service_state::{ReminderPause, ServiceDayState};
+use crate::task::Task;
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A task as kept by a store, including deleted ones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct StoredTask {
+    #[serde(flatten)]
+    pub task: Task,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Keeps everything in memory and forgets it when dropped. Useful for tests
+/// and as the working copy of file-based stores.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    pub(super) tasks: BTreeMap<i64, StoredTask>,
+    pub(super) goals: BTreeMap<NaiveDate, usize>,
+    pub(super) service_states: BTreeMap<NaiveDate, ServiceDayState>,
+    pub(super) pause: ReminderPause,
+    pub(super) events: Vec<Event>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a task that can still be changed
+    fn live_task_mut(&mut self, id: i64) -> Option<&mut Task> {
+        self.tasks
+            .get_mut(&id)
+            .filter(|stored| stored.deleted_at.is_none())
+            .map(|stored| &mut stored.task)
+    }
+
+    fn live_tasks(&self) -> impl Iterator<Item = &Task> {
+        self.tasks.values().filter(|stored| stored.deleted_at.is_none()).map(|stored| &stored.task)
+    }
+
+    /// Append an event about a task that was just changed
+    fn log(&mut self, task_id: i64, kind: EventKind, change: &Change) {
+        let text = self.tasks.get(&task_id).map(|stored| stored.task.text.clone()).unwrap_or_default();
+        let id = self.events.last().map_or(1, |last| last.id + 1);
+        self.events.push(Event {
+            id,
+            at: change.at,
+            day: change.day,
+            task_id,
+            kind,
+            source: change.source,
+            text,
+        });
+    }
+}
+
+impl TaskStore for MemoryStore {
+    fn add_task(&mut self, text: &str, goal: usize, change: &Change) -> Result<i64> {
+        // IDs of deleted tasks are never handed out again
+        let id = self.tasks.keys().next_back().map_or(1, |last| last + 1);
+        let task = Task::new(id, text.to_string(), false, change.day, change.at);
+        self.tasks.insert(id, StoredTask { task, deleted_at: None });
+        self.goals.insert(change.day, goal);
+        self.log(id, EventKind::Add, change);
+        Ok(id)
+    }
+
+    fn get_task(&self, id: i64) -> Result<Option<Task>> {
+        Ok(self.live_tasks().find(|task| task.id == id).cloned())
+    }
+
+    fn tasks_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Task>> {
+        let mut tasks: Vec<Task> = self
+            .live_tasks()
+            .filter(|task| from <= task.day && task.day <= to)
+            .cloned()
+            .collect();
+        tasks.sort_by_key(|task| (task.day, task.id));
+        Ok(tasks)
+    }
+
+    fn complete_task(&mut self, id: i64, goal: usize, change: &Change) -> Result<bool> {
+        match self.live_task_mut(id) {
+            Some(task) if !task.completed => {
+                task.completed = true;
+                task.completed_at = Some(change.at);
+                self.goals.insert(change.day, goal);
+                self.log(id, EventKind::Complete, change);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn uncomplete_task(&mut self, id: i64, change: &Change) -> Result<bool> {
+        match self.live_task_mut(id) {
+            Some(task) if task.completed => {
+                task.mark_incomplete();
+                self.log(id, EventKind::Uncomplete, change);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn update_task_text(&mut self, id: i64, text: &str, change: &Change) -> Result<bool> {
+        match self.live_task_mut(id) {
+            Some(task) => {
+                if task.text != text {
+                    task.text = text.to_string();
+                    self.log(id, EventKind::Edit, change);
+                }
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn reschedule_task(&mut self, id: i64, day: NaiveDate, change: &Change) -> Result<bool> {
+        match self.live_task_mut(id) {
+            Some(task) if !task.completed && task.day != day => {
+                task.day = day;
+                self.log(id, EventKind::Reschedule, change);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn delete_task(&mut self, id: i64, change: &Change) -> Result<bool> {
+        match self.tasks.get_mut(&id) {
+            Some(stored) if stored.deleted_at.is_none() => {
+                stored.deleted_at = Some(change.at);
+                self.log(id, EventKind::Delete, change);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn events_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Event>> {
+        Ok(self.events.iter().filter(|event| from <= event.day && event.day <= to).cloned().collect())
+    }
+
+    fn recorded_goal(&self, day: NaiveDate) -> Result<Option<usize>> {
+        Ok(self.goals.get(&day).copied())
+    }
+
+    fn day_totals(&self) -> Result<Vec<DayTotals>> {
+        let mut totals: BTreeMap<NaiveDate, DayTotals> = BTreeMap::new();
+        for task in self.live_tasks() {
+            let day = totals.entry(task.day).or_insert(DayTotals {
+                day: task.day,
+                completed: 0,
+                total: 0,
+                goal: self.goals.get(&task.day).copied(),
+            });
+            day.total += 1;
+            day.completed += usize::from(task.completed);
+        }
+        Ok(totals.into_values().collect())
+    }
+
+    fn get_service_state(&self, day: NaiveDate) -> Result<ServiceDayState> {
+        Ok(self.service_states.get(&day).cloned().unwrap_or_else(|| ServiceDayState::new(day)))
+    }
+
+    fn save_service_state(&mut self, state: &ServiceDayState) -> Result<()> {
+        self.service_states.insert(state.day, state.clone());
+        Ok(())
+    }
+
+    fn get_reminder_pause(&self) -> Result<ReminderPause> {
+        Ok(self.pause)
+    }
+
+    fn save_reminder_pause(&mut self, pause: &ReminderPause) -> Result<()> {
+        self.pause = *pause;
+        Ok(())
+    }
+
+    fn update_reminder_pause(&mut self, update: &mut dyn FnMut(&mut ReminderPause)) -> Result<ReminderPause> {
+        update(&mut self.pause);
+        Ok(self.pause)
+    }
+}
@@ -0,0 +1,313 @@
+use anyhow::{bail, Context, Result};
+use rusqlite::{Connection, TransactionBehavior};
+
+/// Ordered schema migrations. Entry `i` upgrades the schema from version `i`
+/// to version `i + 1`; never edit a migration once it has shipped, append a
+/// new one instead.
+const MIGRATIONS: &[&str] = &[
+    // 1: initial tasks table. Uses IF NOT EXISTS so databases created before
+    // versioning (user_version 0) are adopted as-is.
+    "CREATE TABLE IF NOT EXISTS tasks (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        text TEXT NOT NULL,
+        completed BOOLEAN NOT NULL DEFAULT FALSE,
+        created_at DATE NOT NULL
+    );",
+    // 2: split the logical day from the creation timestamp and record when
+    // tasks are completed. Pre-existing tasks get midnight of their day as
+    // creation time and no completion time.
+    "CREATE TABLE tasks_v2 (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        text TEXT NOT NULL,
+        completed BOOLEAN NOT NULL DEFAULT FALSE,
+        day DATE NOT NULL,
+        created_at DATETIME NOT NULL,
+        completed_at DATETIME
+    );
+    INSERT INTO tasks_v2 (id, text, completed, day, created_at)
+        SELECT id, text, completed, created_at, created_at || ' 00:00:00' FROM tasks;
+    DROP TABLE tasks;
+    ALTER TABLE tasks_v2 RENAME TO tasks;
+    CREATE INDEX idx_tasks_day ON tasks (day);",
+    // 3: soft-delete, so removed tasks stay in history
+    "ALTER TABLE tasks ADD COLUMN deleted_at DATETIME;",
+    // 4: goal in effect per day. Every day so far was judged against the
+    // fixed goal of 3 tasks.
+    "CREATE TABLE daily_goals (
+        day DATE PRIMARY KEY,
+        goal INTEGER NOT NULL
+    );
+    INSERT INTO daily_goals (day, goal) SELECT DISTINCT day, 3 FROM tasks;",
+    // 5: per-day background service state, so restarts don't repeat
+    // notifications
+    "CREATE TABLE service_state (
+        day DATE PRIMARY KEY,
+        reminders_sent INTEGER NOT NULL DEFAULT 0,
+        last_reminder_at DATETIME,
+        startup_notified BOOLEAN NOT NULL DEFAULT FALSE,
+        reset_notified BOOLEAN NOT NULL DEFAULT FALSE,
+        achievement_notified BOOLEAN NOT NULL DEFAULT FALSE
+    );",
+    // 6: snooze and do-not-disturb, set from the CLI and honored by the
+    // service. A single row, since the pause isn't tied to a day.
+    "CREATE TABLE reminder_pause (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        snoozed_until DATETIME,
+        dnd BOOLEAN NOT NULL DEFAULT FALSE
+    );",
+    // 7: append-only log of task changes. Nothing was recorded before, so it
+    // starts out empty.
+    "CREATE TABLE events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        at DATETIME NOT NULL,
+        day DATE NOT NULL,
+        task_id INTEGER NOT NULL REFERENCES tasks (id),
+        kind TEXT NOT NULL,
+        source TEXT NOT NULL,
+        text TEXT NOT NULL
+    );
+    CREATE INDEX idx_events_day ON events (day);",
+];
+
+/// The schema version this build of threeaday expects
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Read the schema version stored in the database
+pub fn schema_version(conn: &Connection) -> Result<u32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+        .context("Failed to read database schema version")
+}
+
+/// Apply all pending migrations, each in its own transaction
+pub fn migrate(conn: &mut Connection) -> Result<()> {
+    let current = schema_version(conn)?;
+    
+    if current > SCHEMA_VERSION {
+        bail!(
+            "Database schema version {} is newer than supported version {}; please upgrade threeaday",
+            current,
+            SCHEMA_VERSION
+        );
+    }
+    
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = index as u32 + 1;
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        // Another process opening the database at the same time may have
+        // applied this migration while we waited for the lock
+        if schema_version(&tx)? >= version {
+            continue;
+        }
+        tx.execute_batch(sql)
+            .with_context(|| format!("Failed to apply database migration {}", version))?;
+        tx.pragma_update(None, "user_version", version)?;
+        tx.commit()?;
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    /// Schema and data as of `version`: the tasks written by threeaday
+    /// before migrations existed, migrated that far by hand
+    fn fixture_at(version: u32) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE tasks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                text TEXT NOT NULL,
+                completed BOOLEAN NOT NULL DEFAULT FALSE,
+                created_at DATE NOT NULL
+            );
+            INSERT INTO tasks (text, completed, created_at) VALUES ('Old task', TRUE, '2026-09-30');
+            INSERT INTO tasks (text, completed, created_at) VALUES ('Open task', FALSE, '2026-10-01');",
+        )
+        .unwrap();
+        for (index, sql) in MIGRATIONS.iter().enumerate().take(version as usize) {
+            conn.execute_batch(sql).unwrap();
+            conn.pragma_update(None, "user_version", index as u32 + 1).unwrap();
+        }
+        conn
+    }
+
+    fn task_texts(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn.prepare("SELECT text FROM tasks ORDER BY id").unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_migrate_fresh_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), 0);
+        
+        migrate(&mut conn).unwrap();
+        
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        assert!(task_texts(&conn).is_empty());
+    }
+
+    #[test]
+    fn test_migrate_from_v0_keeps_data() {
+        let mut conn = fixture_at(0);
+        
+        migrate(&mut conn).unwrap();
+        
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(task_texts(&conn), vec!["Old task", "Open task"]);
+    }
+
+    #[test]
+    fn test_migrate_from_v1_adds_timestamps() {
+        let mut conn = fixture_at(1);
+        assert_eq!(schema_version(&conn).unwrap(), 1);
+        
+        migrate(&mut conn).unwrap();
+        
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(task_texts(&conn), vec!["Old task", "Open task"]);
+        
+        let (day, created_at, completed_at): (NaiveDate, DateTime<Utc>, Option<DateTime<Utc>>) = conn
+            .query_row(
+                "SELECT day, created_at, completed_at FROM tasks WHERE text = 'Old task'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(day, NaiveDate::from_ymd_opt(2026, 9, 30).unwrap());
+        assert_eq!(created_at, day.and_hms_opt(0, 0, 0).unwrap().and_utc());
+        assert!(completed_at.is_none());
+    }
+
+    #[test]
+    fn test_migrate_from_v2_adds_deleted_at() {
+        let mut conn = fixture_at(2);
+        assert_eq!(schema_version(&conn).unwrap(), 2);
+        
+        migrate(&mut conn).unwrap();
+        
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        let deleted: i64 = conn
+            .query_row("SELECT COUNT(*) FROM tasks WHERE deleted_at IS NOT NULL", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(deleted, 0);
+        assert_eq!(task_texts(&conn), vec!["Old task", "Open task"]);
+    }
+
+    #[test]
+    fn test_migrate_from_v3_backfills_daily_goals() {
+        let mut conn = fixture_at(3);
+        assert_eq!(schema_version(&conn).unwrap(), 3);
+        
+        migrate(&mut conn).unwrap();
+        
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        let goals: Vec<(NaiveDate, i64)> = conn
+            .prepare("SELECT day, goal FROM daily_goals ORDER BY day")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(goals, vec![
+            (NaiveDate::from_ymd_opt(2026, 9, 30).unwrap(), 3),
+            (NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(), 3),
+        ]);
+    }
+
+    #[test]
+    fn test_migrate_from_v4_adds_service_state() {
+        let mut conn = fixture_at(4);
+        assert_eq!(schema_version(&conn).unwrap(), 4);
+        
+        migrate(&mut conn).unwrap();
+        
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        conn.execute("INSERT INTO service_state (day) VALUES ('2026-10-01')", []).unwrap();
+        let (reminders_sent, reset_notified): (u32, bool) = conn
+            .query_row("SELECT reminders_sent, reset_notified FROM service_state", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((reminders_sent, reset_notified), (0, false));
+        assert_eq!(task_texts(&conn), vec!["Old task", "Open task"]);
+    }
+
+    #[test]
+    fn test_migrate_from_v5_adds_reminder_pause() {
+        let mut conn = fixture_at(5);
+        assert_eq!(schema_version(&conn).unwrap(), 5);
+        
+        migrate(&mut conn).unwrap();
+        
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        conn.execute("INSERT INTO reminder_pause (id) VALUES (1)", []).unwrap();
+        let dnd: bool = conn
+            .query_row("SELECT dnd FROM reminder_pause", [], |row| row.get(0))
+            .unwrap();
+        assert!(!dnd);
+        assert!(conn.execute("INSERT INTO reminder_pause (id) VALUES (2)", []).is_err());
+        assert_eq!(task_texts(&conn), vec!["Old task", "Open task"]);
+    }
+
+    #[test]
+    fn test_migrate_from_v6_adds_events() {
+        let mut conn = fixture_at(6);
+        assert_eq!(schema_version(&conn).unwrap(), 6);
+        
+        migrate(&mut conn).unwrap();
+        
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        let events: i64 = conn.query_row("SELECT COUNT(*) FROM events", [], |row| row.get(0)).unwrap();
+        assert_eq!(events, 0);
+        conn.execute(
+            "INSERT INTO events (at, day, task_id, kind, source, text)
+             VALUES ('2026-10-02 09:00:00', '2026-10-02', 1, 'complete', 'cli', 'Old task')",
+            [],
+        )
+        .unwrap();
+        assert_eq!(task_texts(&conn), vec!["Old task", "Open task"]);
+    }
+
+    #[test]
+    fn test_migrate_keeps_autoincrement_ids() {
+        let mut conn = fixture_at(0);
+        
+        migrate(&mut conn).unwrap();
+        conn.execute(
+            "INSERT INTO tasks (text, day, created_at) VALUES ('New task', '2026-10-02', '2026-10-02 09:00:00')",
+            [],
+        )
+        .unwrap();
+        
+        assert_eq!(conn.last_insert_rowid(), 3);
+    }
+
+    #[test]
+    fn test_migrate_is_idempotent() {
+        let mut conn = fixture_at(0);
+        
+        migrate(&mut conn).unwrap();
+        migrate(&mut conn).unwrap();
+        
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(task_texts(&conn).len(), 2);
+    }
+
+    #[test]
+    fn test_migrate_refuses_newer_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1).unwrap();
+        
+        let result = migrate(&mut conn);
+        
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("newer than supported"));
+    }
+}
@@ -0,0 +1,413 @@
+use super::migrations;
+use super::store::{Change, DayTotals, TaskStore};
+use crate::event::{Event, EventKind, EventSource};
+use crate::service_state::{ReminderPause, ServiceDayState};
+use crate::task::Task;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ValueRef};
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension, Row, Transaction, TransactionBehavior};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a statement waits for another process to finish writing before
+/// it fails with "database is locked"
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait before trying again to switch a busy database to WAL
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Columns selected for every task query, in the order `task_from_row` expects
+const TASK_COLUMNS: &str = "id, text, completed, day, created_at, completed_at";
+
+fn task_from_row(row: &Row) -> rusqlite::Result<Task> {
+    Ok(Task {
+        id: row.get(0)?,
+        text: row.get(1)?,
+        completed: row.get(2)?,
+        day: row.get(3)?,
+        created_at: row.get(4)?,
+        completed_at: row.get(5)?,
+    })
+}
+
+// Event kinds and sources are stored by name
+impl FromSql for EventKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|e: anyhow::Error| FromSqlError::Other(e.into()))
+    }
+}
+
+impl FromSql for EventSource {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_str()?.parse().map_err(|e: anyhow::Error| FromSqlError::Other(e.into()))
+    }
+}
+
+fn event_from_row(row: &Row) -> rusqlite::Result<Event> {
+    Ok(Event {
+        id: row.get(0)?,
+        at: row.get(1)?,
+        day: row.get(2)?,
+        task_id: row.get(3)?,
+        kind: row.get(4)?,
+        source: row.get(5)?,
+        text: row.get(6)?,
+    })
+}
+
+/// Append an event about a task that was just changed, with its current text
+fn log_event(conn: &Connection, task_id: i64, kind: EventKind, change: &Change) -> Result<()> {
+    conn.execute(
+        "INSERT INTO events (at, day, task_id, kind, source, text)
+         SELECT ?2, ?3, id, ?4, ?5, text FROM tasks WHERE id = ?1",
+        params![task_id, change.at, change.day, kind.as_str(), change.source.as_str()],
+    )?;
+    Ok(())
+}
+
+/// Remember `goal` as the goal of `day`
+fn record_goal(conn: &Connection, day: NaiveDate, goal: usize) -> Result<()> {
+    conn.execute(
+        "INSERT INTO daily_goals (day, goal) VALUES (?1, ?2)
+         ON CONFLICT (day) DO UPDATE SET goal = excluded.goal",
+        params![day, goal as i64],
+    )?;
+    Ok(())
+}
+
+fn get_reminder_pause(conn: &Connection) -> Result<ReminderPause> {
+    let pause = conn
+        .query_row(
+            "SELECT snoozed_until, dnd FROM reminder_pause WHERE id = 1",
+            [],
+            |row| Ok(ReminderPause { snoozed_until: row.get(0)?, dnd: row.get(1)? }),
+        )
+        .optional()?;
+    Ok(pause.unwrap_or_default())
+}
+
+fn save_reminder_pause(conn: &Connection, pause: &ReminderPause) -> Result<()> {
+    conn.execute(
+        "INSERT INTO reminder_pause (id, snoozed_until, dnd) VALUES (1, ?1, ?2)
+         ON CONFLICT (id) DO UPDATE SET
+            snoozed_until = excluded.snoozed_until,
+            dnd = excluded.dnd",
+        params![pause.snoozed_until, pause.dnd],
+    )?;
+    Ok(())
+}
+
+/// Keeps everything in an SQLite database, the default store
+pub struct SqliteStore {
+    conn: Connection,
+    /// `None` for an in-memory database
+    path: Option<PathBuf>,
+}
+
+impl SqliteStore {
+    /// Open or create the database file at `path` and bring its schema up
+    /// to date
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open database at {}", path.display()))?;
+        Self::configure_file(&conn)
+            .with_context(|| format!("Failed to configure database at {}", path.display()))?;
+        Self::from_connection(conn, Some(path.to_path_buf()))
+            .with_context(|| format!("Failed to migrate database at {}", path.display()))
+    }
+
+    /// Open a new, empty SQLite database that only lives in memory
+    pub fn open_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory().context("Failed to open in-memory database")?;
+        Self::from_connection(conn, None)
+    }
+
+    /// Set up a database file to be shared by the CLI, GUI, service and
+    /// waybar. In WAL mode readers don't block the writer and the writer
+    /// doesn't block readers; writers wait for each other instead of failing
+    /// right away.
+    fn configure_file(conn: &Connection) -> Result<()> {
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        // Switching takes a lock and is remembered in the file; a file system
+        // without WAL support keeps the rollback journal, which also works.
+        // Processes opening a new file at the same time can each hold the
+        // lock the other needs, which SQLite reports as busy right away
+        // instead of waiting, so try again until the timeout.
+        let started = Instant::now();
+        loop {
+            match conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0)) {
+                Ok(_) => break,
+                Err(e) if e.sqlite_error_code() == Some(ErrorCode::DatabaseBusy) && started.elapsed() < BUSY_TIMEOUT => {
+                    thread::sleep(BUSY_RETRY_DELAY);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        Ok(())
+    }
+
+    fn from_connection(mut conn: Connection, path: Option<PathBuf>) -> Result<Self> {
+        migrations::migrate(&mut conn)?;
+        Ok(SqliteStore { conn, path })
+    }
+
+    /// Get the schema version of the open database
+    pub fn schema_version(&self) -> Result<u32> {
+        migrations::schema_version(&self.conn)
+    }
+
+    /// Start a transaction that writes. It takes the write lock right away,
+    /// so it waits for other writers up front instead of failing halfway
+    /// through.
+    fn write_transaction(&mut self) -> Result<Transaction<'_>> {
+        Ok(self.conn.transaction_with_behavior(TransactionBehavior::Immediate)?)
+    }
+}
+
+impl TaskStore for SqliteStore {
+    fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn add_task(&mut self, text: &str, goal: usize, change: &Change) -> Result<i64> {
+        let tx = self.write_transaction()?;
+        tx.execute(
+            "INSERT INTO tasks (text, day, created_at) VALUES (?1, ?2, ?3)",
+            params![text, change.day, change.at],
+        )?;
+        let task_id = tx.last_insert_rowid();
+        record_goal(&tx, change.day, goal)?;
+        log_event(&tx, task_id, EventKind::Add, change)?;
+        tx.commit()?;
+        Ok(task_id)
+    }
+
+    fn get_task(&self, id: i64) -> Result<Option<Task>> {
+        let task = self.conn
+            .query_row(
+                &format!("SELECT {} FROM tasks WHERE id = ?1 AND deleted_at IS NULL", TASK_COLUMNS),
+                params![id],
+                task_from_row,
+            )
+            .optional()?;
+        Ok(task)
+    }
+
+    fn tasks_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Task>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM tasks WHERE day BETWEEN ?1 AND ?2 AND deleted_at IS NULL ORDER BY day, id",
+            TASK_COLUMNS
+        ))?;
+
+        let task_iter = stmt.query_map(params![from, to], task_from_row)?;
+
+        let mut tasks = Vec::new();
+        for task in task_iter {
+            tasks.push(task?);
+        }
+        Ok(tasks)
+    }
+
+    fn complete_task(&mut self, id: i64, goal: usize, change: &Change) -> Result<bool> {
+        let tx = self.write_transaction()?;
+        let rows_affected = tx.execute(
+            "UPDATE tasks SET completed = TRUE, completed_at = ?2 WHERE id = ?1 AND completed = FALSE AND deleted_at IS NULL",
+            params![id, change.at],
+        )?;
+        if rows_affected > 0 {
+            record_goal(&tx, change.day, goal)?;
+            log_event(&tx, id, EventKind::Complete, change)?;
+        }
+        tx.commit()?;
+        Ok(rows_affected > 0)
+    }
+
+    fn uncomplete_task(&mut self, id: i64, change: &Change) -> Result<bool> {
+        let tx = self.write_transaction()?;
+        let rows_affected = tx.execute(
+            "UPDATE tasks SET completed = FALSE, completed_at = NULL WHERE id = ?1 AND completed = TRUE AND deleted_at IS NULL",
+            params![id],
+        )?;
+        if rows_affected > 0 {
+            log_event(&tx, id, EventKind::Uncomplete, change)?;
+        }
+        tx.commit()?;
+        Ok(rows_affected > 0)
+    }
+
+    fn update_task_text(&mut self, id: i64, text: &str, change: &Change) -> Result<bool> {
+        let tx = self.write_transaction()?;
+        let rows_affected = tx.execute(
+            "UPDATE tasks SET text = ?2 WHERE id = ?1 AND text IS NOT ?2 AND deleted_at IS NULL",
+            params![id, text],
+        )?;
+        let exists = if rows_affected > 0 {
+            log_event(&tx, id, EventKind::Edit, change)?;
+            true
+        } else {
+            // The text may already be what was asked for
+            tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ?1 AND deleted_at IS NULL)",
+                params![id],
+                |row| row.get(0),
+            )?
+        };
+        tx.commit()?;
+        Ok(exists)
+    }
+
+    fn reschedule_task(&mut self, id: i64, day: NaiveDate, change: &Change) -> Result<bool> {
+        let tx = self.write_transaction()?;
+        let rows_affected = tx.execute(
+            "UPDATE tasks SET day = ?2 WHERE id = ?1 AND day != ?2 AND completed = FALSE AND deleted_at IS NULL",
+            params![id, day],
+        )?;
+        if rows_affected > 0 {
+            log_event(&tx, id, EventKind::Reschedule, change)?;
+        }
+        tx.commit()?;
+        Ok(rows_affected > 0)
+    }
+
+    fn delete_task(&mut self, id: i64, change: &Change) -> Result<bool> {
+        let tx = self.write_transaction()?;
+        let rows_affected = tx.execute(
+            "UPDATE tasks SET deleted_at = ?2 WHERE id = ?1 AND deleted_at IS NULL",
+            params![id, change.at],
+        )?;
+        if rows_affected > 0 {
+            log_event(&tx, id, EventKind::Delete, change)?;
+        }
+        tx.commit()?;
+        Ok(rows_affected > 0)
+    }
+
+    fn events_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Event>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, at, day, task_id, kind, source, text FROM events
+             WHERE day BETWEEN ?1 AND ?2
+             ORDER BY id"
+        )?;
+
+        let events = stmt.query_map(params![from, to], event_from_row)?;
+        Ok(events.collect::<rusqlite::Result<_>>()?)
+    }
+
+    fn recorded_goal(&self, day: NaiveDate) -> Result<Option<usize>> {
+        let goal: Option<i64> = self.conn
+            .query_row("SELECT goal FROM daily_goals WHERE day = ?1", params![day], |row| row.get(0))
+            .optional()?;
+        Ok(goal.map(|goal| goal as usize))
+    }
+
+    fn day_totals(&self) -> Result<Vec<DayTotals>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.day, SUM(t.completed), COUNT(*), g.goal
+             FROM tasks t LEFT JOIN daily_goals g ON g.day = t.day
+             WHERE t.deleted_at IS NULL
+             GROUP BY t.day
+             ORDER BY t.day"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(DayTotals {
+                day: row.get(0)?,
+                completed: row.get::<_, i64>(1)? as usize,
+                total: row.get::<_, i64>(2)? as usize,
+                goal: row.get::<_, Option<i64>>(3)?.map(|goal| goal as usize),
+            })
+        })?;
+
+        let mut totals = Vec::new();
+        for row in rows {
+            totals.push(row?);
+        }
+        Ok(totals)
+    }
+
+    fn get_service_state(&self, day: NaiveDate) -> Result<ServiceDayState> {
+        let state = self.conn
+            .query_row(
+                "SELECT reminders_sent, last_reminder_at, startup_notified, reset_notified, achievement_notified
+                 FROM service_state WHERE day = ?1",
+                params![day],
+                |row| {
+                    Ok(ServiceDayState {
+                        day,
+                        reminders_sent: row.get(0)?,
+                        last_reminder_at: row.get(1)?,
+                        startup_notified: row.get(2)?,
+                        reset_notified: row.get(3)?,
+                        achievement_notified: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(state.unwrap_or_else(|| ServiceDayState::new(day)))
+    }
+
+    fn save_service_state(&mut self, state: &ServiceDayState) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO service_state
+                (day, reminders_sent, last_reminder_at, startup_notified, reset_notified, achievement_notified)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT (day) DO UPDATE SET
+                reminders_sent = excluded.reminders_sent,
+                last_reminder_at = excluded.last_reminder_at,
+                startup_notified = excluded.startup_notified,
+                reset_notified = excluded.reset_notified,
+                achievement_notified = excluded.achievement_notified",
+            params![
+                state.day,
+                state.reminders_sent,
+                state.last_reminder_at,
+                state.startup_notified,
+                state.reset_notified,
+                state.achievement_notified,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn get_reminder_pause(&self) -> Result<ReminderPause> {
+        get_reminder_pause(&self.conn)
+    }
+
+    fn save_reminder_pause(&mut self, pause: &ReminderPause) -> Result<()> {
+        save_reminder_pause(&self.conn, pause)
+    }
+
+    fn update_reminder_pause(&mut self, update: &mut dyn FnMut(&mut ReminderPause)) -> Result<ReminderPause> {
+        let tx = self.write_transaction()?;
+        let mut pause = get_reminder_pause(&tx)?;
+        update(&mut pause);
+        save_reminder_pause(&tx, &pause)?;
+        tx.commit()?;
+        Ok(pause)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_new_database_is_at_current_schema_version() {
+        let store = SqliteStore::open_in_memory().unwrap();
+
+        assert_eq!(store.schema_version().unwrap(), migrations::SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_file_database_uses_wal() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SqliteStore::open(&temp_dir.path().join("tasks.db")).unwrap();
+
+        let mode: String = store.conn.pragma_query_value(None, "journal_mode", |row| row.get(0)).unwrap();
+        assert_eq!(mode, "wal");
+    }
+}
@@ -0,0 +1,92 @@
+use crate::event::{Event, EventSource};
+use crate::service_state::{ReminderPause, ServiceDayState};
+use crate::task::Task;
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::path::Path;
+
+/// Task counts of one logical day, as kept by a [`TaskStore`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayTotals {
+    pub day: NaiveDate,
+    pub completed: usize,
+    pub total: usize,
+    /// The goal recorded for the day, if any
+    pub goal: Option<usize>,
+}
+
+/// When and by whom a task is changed, for the event log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Change {
+    pub at: DateTime<Utc>,
+    /// The logical day at `at`
+    pub day: NaiveDate,
+    pub source: EventSource,
+}
+
+/// Where tasks and the service's bookkeeping are kept.
+///
+/// A store only saves and loads; which day it is, which goal applies and how
+/// streaks are counted is decided by [`Database`](super::Database). Deleted
+/// tasks stay in the store but are never returned or changed again. Every
+/// change to a task is appended to the event log along with the change
+/// itself, and only if the task did change.
+pub trait TaskStore: Send {
+    /// Get the file the store keeps its data in, or `None` if it is in memory
+    fn path(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Add an open task to the day of the change and record `goal` as the
+    /// goal of that day, returning the new task's ID
+    fn add_task(&mut self, text: &str, goal: usize, change: &Change) -> Result<i64>;
+
+    /// Get a single task by ID
+    fn get_task(&self, id: i64) -> Result<Option<Task>>;
+
+    /// Get the tasks of all days from `from` to `to`, inclusive, ordered by
+    /// day and then by ID
+    fn tasks_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Task>>;
+
+    /// Mark an open task completed and record `goal` as the goal of the day
+    /// of the change. Returns whether the task was open; if it wasn't,
+    /// nothing is recorded.
+    fn complete_task(&mut self, id: i64, goal: usize, change: &Change) -> Result<bool>;
+
+    /// Mark a completed task as open again. Returns whether it was completed.
+    fn uncomplete_task(&mut self, id: i64, change: &Change) -> Result<bool>;
+
+    /// Replace the text of a task. Returns whether the task exists; giving it
+    /// the text it already has isn't a change.
+    fn update_task_text(&mut self, id: i64, text: &str, change: &Change) -> Result<bool>;
+
+    /// Move an open task to `day`. Returns whether the task is open and was
+    /// on another day.
+    fn reschedule_task(&mut self, id: i64, day: NaiveDate, change: &Change) -> Result<bool>;
+
+    /// Hide a task from now on. Returns whether it wasn't deleted already.
+    fn delete_task(&mut self, id: i64, change: &Change) -> Result<bool>;
+
+    /// Get the events of all days from `from` to `to`, inclusive, in the
+    /// order they happened
+    fn events_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Event>>;
+
+    /// Get the goal recorded for a day
+    fn recorded_goal(&self, day: NaiveDate) -> Result<Option<usize>>;
+
+    /// Get the task counts of every day that has tasks, ordered by day
+    fn day_totals(&self) -> Result<Vec<DayTotals>>;
+
+    /// Get what the service has done on a day, or a fresh state if nothing
+    fn get_service_state(&self, day: NaiveDate) -> Result<ServiceDayState>;
+
+    fn save_service_state(&mut self, state: &ServiceDayState) -> Result<()>;
+
+    fn get_reminder_pause(&self) -> Result<ReminderPause>;
+
+    fn save_reminder_pause(&mut self, pause: &ReminderPause) -> Result<()>;
+
+    /// Change the reminder pause without other writers getting in between
+    /// reading and saving it, and return the result
+    fn update_reminder_pause(&mut self, update: &mut dyn FnMut(&mut ReminderPause)) -> Result<ReminderPause>;
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// What happened to a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Add,
+    Complete,
+    Uncomplete,
+    Edit,
+    Delete,
+    /// The task was moved to another day
+    Reschedule,
+}
+
+/// Which part of threeaday changed a task
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSource {
+    #[default]
+    Cli,
+    Gui,
+    Service,
+    /// A button on one of the service's notifications
+    Notification,
+}
+
+/// One entry of the append-only log of task changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    pub id: i64,
+    pub at: DateTime<Utc>,
+    /// Logical day the change was made on
+    pub day: NaiveDate,
+    pub task_id: i64,
+    pub kind: EventKind,
+    pub source: EventSource,
+    /// Text of the task after the change
+    pub text: String,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 6] = [
+        EventKind::Add,
+        EventKind::Complete,
+        EventKind::Uncomplete,
+        EventKind::Edit,
+        EventKind::Delete,
+        EventKind::Reschedule,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Add => "add",
+            EventKind::Complete => "complete",
+            EventKind::Uncomplete => "uncomplete",
+            EventKind::Edit => "edit",
+            EventKind::Delete => "delete",
+            EventKind::Reschedule => "reschedule",
+        }
+    }
+}
+
+impl EventSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventSource::Cli => "cli",
+            EventSource::Gui => "gui",
+            EventSource::Service => "service",
+            EventSource::Notification => "notification",
+        }
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for EventSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EventKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EventKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown event kind '{}'", s))
+    }
+}
+
+impl FromStr for EventSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [EventSource::Cli, EventSource::Gui, EventSource::Service, EventSource::Notification]
+            .into_iter()
+            .find(|source| source.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown event source '{}'", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_roundtrip() {
+        for kind in EventKind::ALL {
+            assert_eq!(kind.as_str().parse::<EventKind>().unwrap(), kind);
+            assert_eq!(serde_json::to_string(&kind).unwrap(), format!("\"{}\"", kind));
+        }
+        for source in [EventSource::Cli, EventSource::Gui, EventSource::Service, EventSource::Notification] {
+            assert_eq!(source.as_str().parse::<EventSource>().unwrap(), source);
+            assert_eq!(serde_json::to_string(&source).unwrap(), format!("\"{}\"", source));
+        }
+        assert_eq!("reschedule".parse::<EventKind>().unwrap(), EventKind::Reschedule);
+        assert!("move".parse::<EventKind>().is_err());
+    }
+}
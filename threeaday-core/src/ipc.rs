@@ -0,0 +1,155 @@
+//! The local interface of the background service.
+//!
+//! The service listens on a Unix socket and speaks newline-delimited JSON:
+//! each line a client writes is a [`Request`], answered by one [`Response`]
+//! line. After a `Subscribe` request the connection stays open and the
+//! service writes a [`Signal`] line for every event until the client
+//! disconnects.
+
+use crate::utils::{get_data_dir, get_project_dirs};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a client waits for the service to answer
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A call to the running service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method")]
+pub enum Request {
+    /// Get a [`ServiceStatus`]
+    GetStatus,
+    /// Pause reminders for `minutes`, or the service's default snooze
+    Snooze { minutes: Option<u32> },
+    /// Load the config file again
+    ReloadConfig,
+    /// Send a reminder right away, regardless of the interval or a pause
+    TriggerReminder,
+    /// Keep the connection open and receive a [`Signal`] for every event
+    Subscribe,
+}
+
+/// The service's answer to a [`Request`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum Response {
+    Ok,
+    Status(ServiceStatus),
+    Error { message: String },
+}
+
+/// An event the service announces to subscribers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "signal")]
+pub enum Signal {
+    /// A task of the current day was completed, from anywhere
+    TaskCompleted { completed: usize, total: usize },
+    /// The daily goal was reached
+    GoalAchieved { completed: usize },
+}
+
+/// What the running service is up to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceStatus {
+    pub started_at: DateTime<Utc>,
+    /// The logical day the service is on
+    pub day: NaiveDate,
+    pub reminders_sent: u32,
+    pub max_reminders: u32,
+    pub last_reminder_at: Option<DateTime<Utc>>,
+    /// When the next reminder goes out, or `None` if none is scheduled today
+    pub next_reminder_at: Option<DateTime<Utc>>,
+    /// The last logical day the daily reset was announced on
+    pub last_reset: Option<NaiveDate>,
+    /// Whether quiet hours, a snooze or do-not-disturb hold back reminders
+    pub paused: bool,
+}
+
+/// Get the location of the service socket, in the runtime directory when
+/// there is one
+pub fn socket_path() -> Result<PathBuf> {
+    let dir = match get_project_dirs()?.runtime_dir() {
+        Some(dir) => dir.to_path_buf(),
+        None => get_data_dir()?,
+    };
+    Ok(dir.join("service.sock"))
+}
+
+/// Send a request to the running service and wait for its response
+pub fn request(request: &Request) -> Result<Response> {
+    request_at(&socket_path()?, request)
+}
+
+/// Send a request to the service listening at `path`
+pub fn request_at(path: &Path, request: &Request) -> Result<Response> {
+    if *request == Request::Subscribe {
+        bail!("Subscribing needs a connection that stays open");
+    }
+
+    let mut stream = UnixStream::connect(path)
+        .with_context(|| format!("Service is not running (no socket at {})", path.display()))?;
+    stream.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+
+    let mut line = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut line)
+        .context("Service did not answer")?;
+    serde_json::from_str(&line).with_context(|| format!("Invalid response from service: {}", line.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_wire_format() {
+        assert_eq!(serde_json::to_string(&Request::GetStatus).unwrap(), r#"{"method":"GetStatus"}"#);
+        assert_eq!(
+            serde_json::from_str::<Request>(r#"{"method":"Snooze","minutes":10}"#).unwrap(),
+            Request::Snooze { minutes: Some(10) }
+        );
+        assert_eq!(
+            serde_json::to_string(&Response::Error { message: "nope".to_string() }).unwrap(),
+            r#"{"result":"error","message":"nope"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Signal::GoalAchieved { completed: 3 }).unwrap(),
+            r#"{"signal":"GoalAchieved","completed":3}"#
+        );
+    }
+
+    #[test]
+    fn test_request_at() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("service.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            writeln!(stream, r#"{{"result":"ok"}}"#).unwrap();
+            line
+        });
+
+        let response = request_at(&path, &Request::TriggerReminder).unwrap();
+        assert_eq!(response, Response::Ok);
+        assert_eq!(server.join().unwrap().trim(), r#"{"method":"TriggerReminder"}"#);
+    }
+
+    #[test]
+    fn test_request_without_service() {
+        let temp_dir = TempDir::new().unwrap();
+        let error = request_at(&temp_dir.path().join("service.sock"), &Request::GetStatus).unwrap_err();
+
+        assert!(error.to_string().contains("Service is not running"));
+    }
+}
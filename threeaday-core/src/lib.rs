@@ -1,12 +1,26 @@
+pub mod clock;
 pub mod db;
 pub mod task;
 pub mod config;
+pub mod day;
+pub mod event;
+pub mod ipc;
+pub mod quiet_hours;
+pub mod service_state;
+pub mod streak;
+pub mod summary;
 pub mod utils;
 
 // Re-export commonly used types
-pub use db::Database;
+pub use clock::{Clock, FakeClock, SystemClock, Zone};
+pub use db::{Database, TaskStore};
 pub use task::Task;
+pub use event::{Event, EventKind, EventSource};
 pub use config::Config;
+pub use quiet_hours::QuietHours;
+pub use service_state::{ReminderPause, ServiceDayState};
+pub use streak::Streak;
+pub use summary::DaySummary;
 
 // Re-export common dependencies
 pub use anyhow::{Result, Context};
@@ -0,0 +1,139 @@
+use crate::config::hhmm;
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// A range of local time during which the service sends no reminders
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    /// Start of the range (HH:MM in the config file)
+    #[serde(with = "hhmm")]
+    pub start: NaiveTime,
+    /// End of the range; a range ending before it starts spans midnight
+    #[serde(with = "hhmm")]
+    pub end: NaiveTime,
+    /// Days the range starts on; empty means every day
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<Weekday>,
+}
+
+impl QuietHours {
+    /// Quiet hours on every day of the week
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self { start, end, days: Vec::new() }
+    }
+
+    fn starts_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    /// Check whether a local wall-clock time falls into this range
+    pub fn contains(&self, local: NaiveDateTime) -> bool {
+        let (start, end) = (self.start, self.end);
+        let time = local.time();
+        let day = local.date().weekday();
+
+        if start <= end {
+            self.starts_on(day) && start <= time && time < end
+        } else {
+            // Spans midnight: the early morning part belongs to the range that
+            // started the evening before
+            (time >= start && self.starts_on(day)) || (time < end && self.starts_on(day.pred()))
+        }
+    }
+
+    /// Get when the range containing `local` ends, or `None` if it doesn't
+    /// contain it
+    pub fn end_after(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        if !self.contains(local) {
+            return None;
+        }
+
+        let date = local.date();
+        if self.start > self.end && local.time() >= self.start {
+            Some((date + Duration::days(1)).and_time(self.end))
+        } else {
+            Some(date.and_time(self.end))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        // 2026-10-16 is a Friday
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn hours(start: &str, end: &str) -> QuietHours {
+        let time = |s| NaiveTime::parse_from_str(s, "%H:%M").unwrap();
+        QuietHours::new(time(start), time(end))
+    }
+
+    #[test]
+    fn test_daytime_range() {
+        let quiet = hours("12:00", "13:30");
+
+        assert!(!quiet.contains(at("2026-10-16 11:59")));
+        assert!(quiet.contains(at("2026-10-16 12:00")));
+        assert!(quiet.contains(at("2026-10-16 13:29")));
+        assert!(!quiet.contains(at("2026-10-16 13:30")));
+    }
+
+    #[test]
+    fn test_overnight_range() {
+        let quiet = hours("22:00", "08:00");
+
+        assert!(quiet.contains(at("2026-10-16 23:00")));
+        assert!(quiet.contains(at("2026-10-17 07:59")));
+        assert!(!quiet.contains(at("2026-10-17 08:00")));
+        assert!(!quiet.contains(at("2026-10-16 21:59")));
+    }
+
+    #[test]
+    fn test_weekday_range() {
+        let quiet = QuietHours {
+            days: vec![Weekday::Mon, Weekday::Fri],
+            ..hours("09:00", "10:00")
+        };
+
+        assert!(quiet.contains(at("2026-10-16 09:30")));
+        assert!(!quiet.contains(at("2026-10-17 09:30")));
+        assert!(quiet.contains(at("2026-10-19 09:30")));
+    }
+
+    #[test]
+    fn test_overnight_weekday_range_belongs_to_start_day() {
+        // Friday night until Saturday morning only
+        let quiet = QuietHours {
+            days: vec![Weekday::Fri],
+            ..hours("23:00", "10:00")
+        };
+
+        assert!(quiet.contains(at("2026-10-16 23:30")));
+        assert!(quiet.contains(at("2026-10-17 09:00")));
+        assert!(!quiet.contains(at("2026-10-16 09:00")));
+        assert!(!quiet.contains(at("2026-10-17 23:30")));
+    }
+
+    #[test]
+    fn test_end_after() {
+        let quiet = hours("22:00", "08:00");
+
+        assert_eq!(quiet.end_after(at("2026-10-16 23:00")), Some(at("2026-10-17 08:00")));
+        assert_eq!(quiet.end_after(at("2026-10-17 03:00")), Some(at("2026-10-17 08:00")));
+        assert_eq!(quiet.end_after(at("2026-10-17 12:00")), None);
+        assert_eq!(hours("12:00", "13:00").end_after(at("2026-10-16 12:30")), Some(at("2026-10-16 13:00")));
+    }
+
+    #[test]
+    fn test_times_in_toml() {
+        let parsed: QuietHours = toml::from_str("start = \"22:00\"\nend = \"07:30\"").unwrap();
+        assert_eq!(parsed, hours("22:00", "07:30"));
+        assert_eq!(toml::to_string(&parsed).unwrap(), "start = \"22:00\"\nend = \"07:30\"\n");
+
+        let error = toml::from_str::<QuietHours>("start = \"25:00\"\nend = \"08:00\"").unwrap_err();
+        assert!(error.message().contains("invalid time '25:00'"));
+    }
+}
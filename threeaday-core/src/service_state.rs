@@ -0,0 +1,71 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What the background service has already done on a logical day. Persisted
+/// so restarts don't repeat once-a-day notifications or reset the reminder cap.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceDayState {
+    pub day: NaiveDate,
+    pub reminders_sent: u32,
+    pub last_reminder_at: Option<DateTime<Utc>>,
+    pub startup_notified: bool,
+    pub reset_notified: bool,
+    pub achievement_notified: bool,
+}
+
+impl ServiceDayState {
+    /// State of a day the service hasn't done anything on yet
+    pub fn new(day: NaiveDate) -> Self {
+        Self {
+            day,
+            reminders_sent: 0,
+            last_reminder_at: None,
+            startup_notified: false,
+            reset_notified: false,
+            achievement_notified: false,
+        }
+    }
+}
+
+/// Reminders paused from the CLI, either for a while or until turned off
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReminderPause {
+    pub snoozed_until: Option<DateTime<Utc>>,
+    pub dnd: bool,
+}
+
+impl ReminderPause {
+    /// Check whether reminders are paused at `now`
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.dnd || self.snoozed_until.is_some_and(|until| now < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_reminder_pause_default_is_inactive() {
+        assert!(!ReminderPause::default().is_active(Utc::now()));
+    }
+
+    #[test]
+    fn test_reminder_pause_snooze_expires() {
+        let now = Utc::now();
+        let pause = ReminderPause { snoozed_until: Some(now + Duration::hours(2)), dnd: false };
+        
+        assert!(pause.is_active(now));
+        assert!(pause.is_active(now + Duration::minutes(119)));
+        assert!(!pause.is_active(now + Duration::hours(2)));
+    }
+
+    #[test]
+    fn test_reminder_pause_dnd_never_expires() {
+        let now = Utc::now();
+        let pause = ReminderPause { snoozed_until: None, dnd: true };
+        
+        assert!(pause.is_active(now + Duration::days(30)));
+    }
+}
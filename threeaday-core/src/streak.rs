@@ -0,0 +1,132 @@
+use crate::config::Config;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Current and longest run of days on which the daily goal was achieved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Streak {
+    pub current: u32,
+    pub longest: u32,
+}
+
+/// Walk every logical day from `first_day` to `today` and compute the streak.
+///
+/// Days listed in `config.rest_days` neither extend nor break a streak unless
+/// the goal was achieved anyway. Up to `config.grace_days` missed days in a
+/// row are forgiven. Today only counts once the goal is achieved, so an
+/// unfinished today never breaks the streak.
+pub fn calculate_streak(
+    achieved_days: &BTreeSet<NaiveDate>,
+    first_day: NaiveDate,
+    today: NaiveDate,
+    config: &Config,
+) -> Streak {
+    let mut streak = Streak::default();
+    let mut missed_in_a_row = 0;
+    
+    for day in first_day.iter_days().take_while(|day| *day <= today) {
+        if achieved_days.contains(&day) {
+            streak.current += 1;
+            missed_in_a_row = 0;
+        } else if day == today || config.rest_days.contains(&day.weekday()) {
+            continue;
+        } else {
+            missed_in_a_row += 1;
+            if missed_in_a_row > config.grace_days {
+                streak.current = 0;
+            }
+        }
+        streak.longest = streak.longest.max(streak.current);
+    }
+    
+    streak
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Weekday;
+
+    fn day(d: u32) -> NaiveDate {
+        // October 2026 starts on a Thursday
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn days(ds: &[u32]) -> BTreeSet<NaiveDate> {
+        ds.iter().map(|d| day(*d)).collect()
+    }
+
+    fn strict_config() -> Config {
+        Config {
+            grace_days: 0,
+            rest_days: Vec::new(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_no_history() {
+        let streak = calculate_streak(&BTreeSet::new(), day(5), day(5), &strict_config());
+        assert_eq!(streak, Streak { current: 0, longest: 0 });
+    }
+
+    #[test]
+    fn test_consecutive_days() {
+        let streak = calculate_streak(&days(&[1, 2, 3, 4]), day(1), day(4), &strict_config());
+        assert_eq!(streak, Streak { current: 4, longest: 4 });
+    }
+
+    #[test]
+    fn test_unfinished_today_does_not_break_streak() {
+        let streak = calculate_streak(&days(&[1, 2, 3]), day(1), day(4), &strict_config());
+        assert_eq!(streak, Streak { current: 3, longest: 3 });
+    }
+
+    #[test]
+    fn test_missed_day_breaks_streak() {
+        let streak = calculate_streak(&days(&[1, 2, 3, 5, 6]), day(1), day(6), &strict_config());
+        assert_eq!(streak, Streak { current: 2, longest: 3 });
+    }
+
+    #[test]
+    fn test_missed_yesterday_resets_current() {
+        let streak = calculate_streak(&days(&[1, 2]), day(1), day(4), &strict_config());
+        assert_eq!(streak, Streak { current: 0, longest: 2 });
+    }
+
+    #[test]
+    fn test_grace_day_forgives_single_miss() {
+        let config = Config { grace_days: 1, ..strict_config() };
+        let streak = calculate_streak(&days(&[1, 2, 3, 5, 6]), day(1), day(6), &config);
+        assert_eq!(streak, Streak { current: 5, longest: 5 });
+    }
+
+    #[test]
+    fn test_grace_days_exceeded() {
+        let config = Config { grace_days: 1, ..strict_config() };
+        let streak = calculate_streak(&days(&[1, 2, 5, 6]), day(1), day(6), &config);
+        assert_eq!(streak, Streak { current: 2, longest: 2 });
+    }
+
+    #[test]
+    fn test_rest_days_are_skipped() {
+        // 3rd and 4th are Saturday and Sunday
+        let config = Config {
+            rest_days: vec![Weekday::Sat, Weekday::Sun],
+            ..strict_config()
+        };
+        let streak = calculate_streak(&days(&[1, 2, 5, 6]), day(1), day(6), &config);
+        assert_eq!(streak, Streak { current: 4, longest: 4 });
+    }
+
+    #[test]
+    fn test_achieved_rest_day_still_counts() {
+        let config = Config {
+            rest_days: vec![Weekday::Sat, Weekday::Sun],
+            ..strict_config()
+        };
+        let streak = calculate_streak(&days(&[1, 2, 3, 5]), day(1), day(5), &config);
+        assert_eq!(streak, Streak { current: 4, longest: 4 });
+    }
+}
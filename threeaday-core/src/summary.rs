@@ -0,0 +1,58 @@
+use crate::utils::is_daily_goal_achieved;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Progress of a single logical day against the goal in effect that day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaySummary {
+    pub day: NaiveDate,
+    pub completed: usize,
+    pub total: usize,
+    pub goal: usize,
+}
+
+impl DaySummary {
+    pub fn is_goal_achieved(&self) -> bool {
+        is_daily_goal_achieved(self.completed, self.goal)
+    }
+    
+    /// Number of tasks still to complete to reach the goal
+    pub fn remaining(&self) -> usize {
+        self.goal.saturating_sub(self.completed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(completed: usize, total: usize, goal: usize) -> DaySummary {
+        DaySummary {
+            day: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            completed,
+            total,
+            goal,
+        }
+    }
+
+    #[test]
+    fn test_goal_not_achieved() {
+        let summary = summary(1, 3, 3);
+        assert!(!summary.is_goal_achieved());
+        assert_eq!(summary.remaining(), 2);
+    }
+
+    #[test]
+    fn test_goal_achieved() {
+        let summary = summary(5, 6, 5);
+        assert!(summary.is_goal_achieved());
+        assert_eq!(summary.remaining(), 0);
+    }
+
+    #[test]
+    fn test_goal_exceeded() {
+        let summary = summary(4, 4, 1);
+        assert!(summary.is_goal_achieved());
+        assert_eq!(summary.remaining(), 0);
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,16 +6,23 @@ pub struct Task {
     pub id: i64,
     pub text: String,
     pub completed: bool,
-    pub created_at: NaiveDate,
+    /// Logical day the task belongs to
+    pub day: NaiveDate,
+    /// When the task was added
+    pub created_at: DateTime<Utc>,
+    /// When the task was completed, if it has been
+    pub completed_at: Option<DateTime<Utc>>,
 }
 
 impl Task {
-    pub fn new(id: i64, text: String, completed: bool, created_at: NaiveDate) -> Self {
+    pub fn new(id: i64, text: String, completed: bool, day: NaiveDate, created_at: DateTime<Utc>) -> Self {
         Self {
             id,
             text,
             completed,
+            day,
             created_at,
+            completed_at: None,
         }
     }
     
@@ -25,6 +32,19 @@ impl Task {
     
     pub fn mark_completed(&mut self) {
         self.completed = true;
+        if self.completed_at.is_none() {
+            self.completed_at = Some(Utc::now());
+        }
+    }
+    
+    pub fn mark_incomplete(&mut self) {
+        self.completed = false;
+        self.completed_at = None;
+    }
+    
+    /// How long the task sat open before it was completed
+    pub fn time_to_complete(&self) -> Option<Duration> {
+        self.completed_at.map(|completed_at| completed_at - self.created_at)
     }
 }
 
@@ -35,46 +55,53 @@ mod tests {
 
     #[test]
     fn test_task_new() {
-        let today = Utc::now().date_naive();
-        let task = Task::new(1, "Test task".to_string(), false, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let task = Task::new(1, "Test task".to_string(), false, today, now);
         
         assert_eq!(task.id, 1);
         assert_eq!(task.text, "Test task");
         assert!(!task.completed);
-        assert_eq!(task.created_at, today);
+        assert_eq!(task.day, today);
+        assert_eq!(task.created_at, now);
     }
 
     #[test]
     fn test_task_new_completed() {
-        let today = Utc::now().date_naive();
-        let task = Task::new(42, "Completed task".to_string(), true, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let task = Task::new(42, "Completed task".to_string(), true, today, now);
         
         assert_eq!(task.id, 42);
         assert_eq!(task.text, "Completed task");
         assert!(task.completed);
-        assert_eq!(task.created_at, today);
+        assert_eq!(task.day, today);
+        assert_eq!(task.created_at, now);
     }
 
     #[test]
     fn test_is_completed_false() {
-        let today = Utc::now().date_naive();
-        let task = Task::new(1, "Incomplete task".to_string(), false, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let task = Task::new(1, "Incomplete task".to_string(), false, today, now);
         
         assert!(!task.is_completed());
     }
 
     #[test]
     fn test_is_completed_true() {
-        let today = Utc::now().date_naive();
-        let task = Task::new(1, "Complete task".to_string(), true, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let task = Task::new(1, "Complete task".to_string(), true, today, now);
         
         assert!(task.is_completed());
     }
 
     #[test]
     fn test_mark_completed() {
-        let today = Utc::now().date_naive();
-        let mut task = Task::new(1, "Task to complete".to_string(), false, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let mut task = Task::new(1, "Task to complete".to_string(), false, today, now);
         
         assert!(!task.is_completed());
         
@@ -86,8 +113,9 @@ mod tests {
 
     #[test]
     fn test_mark_completed_idempotent() {
-        let today = Utc::now().date_naive();
-        let mut task = Task::new(1, "Already complete".to_string(), true, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let mut task = Task::new(1, "Already complete".to_string(), true, today, now);
         
         assert!(task.is_completed());
         
@@ -97,22 +125,37 @@ mod tests {
         assert!(task.completed);
     }
 
+    #[test]
+    fn test_mark_incomplete() {
+        let now = Utc::now();
+        let mut task = Task::new(1, "Mis-clicked task".to_string(), false, now.date_naive(), now);
+        task.mark_completed();
+        
+        task.mark_incomplete();
+        
+        assert!(!task.is_completed());
+        assert!(task.completed_at.is_none());
+    }
+
     #[test]
     fn test_task_clone() {
-        let today = Utc::now().date_naive();
-        let task = Task::new(5, "Original task".to_string(), false, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let task = Task::new(5, "Original task".to_string(), false, today, now);
         let cloned_task = task.clone();
         
         assert_eq!(task.id, cloned_task.id);
         assert_eq!(task.text, cloned_task.text);
         assert_eq!(task.completed, cloned_task.completed);
+        assert_eq!(task.day, cloned_task.day);
         assert_eq!(task.created_at, cloned_task.created_at);
     }
 
     #[test]
     fn test_task_fields_independent_after_clone() {
-        let today = Utc::now().date_naive();
-        let original = Task::new(1, "Original".to_string(), false, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let original = Task::new(1, "Original".to_string(), false, today, now);
         let mut cloned = original.clone();
         
         // Modify clone
@@ -125,8 +168,9 @@ mod tests {
 
     #[test] 
     fn test_task_serialization() {
-        let today = Utc::now().date_naive();
-        let task = Task::new(1, "Serializable task".to_string(), true, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let task = Task::new(1, "Serializable task".to_string(), true, today, now);
         
         // Test JSON serialization
         let json = serde_json::to_string(&task).unwrap();
@@ -138,14 +182,17 @@ mod tests {
         assert_eq!(task.id, deserialized.id);
         assert_eq!(task.text, deserialized.text);
         assert_eq!(task.completed, deserialized.completed);
+        assert_eq!(task.day, deserialized.day);
         assert_eq!(task.created_at, deserialized.created_at);
+        assert_eq!(task.completed_at, deserialized.completed_at);
     }
 
     #[test]
     fn test_task_with_special_characters() {
-        let today = Utc::now().date_naive();
+        let now = Utc::now();
+        let today = now.date_naive();
         let special_text = "Task with émojis 🎯 and unicode ñ";
-        let task = Task::new(999, special_text.to_string(), false, today);
+        let task = Task::new(999, special_text.to_string(), false, today, now);
         
         assert_eq!(task.text, special_text);
         
@@ -157,10 +204,52 @@ mod tests {
 
     #[test]
     fn test_task_empty_text() {
-        let today = Utc::now().date_naive();
-        let task = Task::new(0, "".to_string(), false, today);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let task = Task::new(0, "".to_string(), false, today, now);
         
         assert_eq!(task.text, "");
         assert_eq!(task.id, 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_new_task_has_no_completed_at() {
+        let now = Utc::now();
+        let task = Task::new(1, "Fresh task".to_string(), false, now.date_naive(), now);
+        
+        assert!(task.completed_at.is_none());
+        assert!(task.time_to_complete().is_none());
+    }
+
+    #[test]
+    fn test_mark_completed_sets_completed_at() {
+        let now = Utc::now();
+        let mut task = Task::new(1, "Timed task".to_string(), false, now.date_naive(), now);
+        
+        task.mark_completed();
+        
+        assert!(task.completed_at.is_some());
+        assert!(task.time_to_complete().unwrap() >= Duration::zero());
+    }
+
+    #[test]
+    fn test_mark_completed_keeps_original_completed_at() {
+        let now = Utc::now();
+        let mut task = Task::new(1, "Timed task".to_string(), false, now.date_naive(), now);
+        let first_completed = now + Duration::minutes(5);
+        task.completed_at = Some(first_completed);
+        
+        task.mark_completed();
+        
+        assert_eq!(task.completed_at, Some(first_completed));
+    }
+
+    #[test]
+    fn test_time_to_complete() {
+        let now = Utc::now();
+        let mut task = Task::new(1, "Walk".to_string(), true, now.date_naive(), now);
+        task.completed_at = Some(now + Duration::minutes(90));
+        
+        assert_eq!(task.time_to_complete(), Some(Duration::minutes(90)));
+    }
+}
@@ -1,8 +1,11 @@
-use anyhow::{Context, Result};
+use crate::clock::Zone;
+use crate::task::Task;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use directories::ProjectDirs;
 use std::path::PathBuf;
 
-/// The daily goal - completing 3 tasks
+/// The default daily goal - completing 3 tasks
 pub const DAILY_GOAL_COMPLETION_COUNT: usize = 3;
 
 /// Get the project directories for threeaday
@@ -28,23 +31,115 @@ pub fn format_task_status(completed: bool) -> &'static str {
     if completed { "✓" } else { " " }
 }
 
+/// Format a duration compactly, e.g. "45m", "1h 28m" or "2d 3h"
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    if minutes < 1 {
+        "<1m".to_string()
+    } else if minutes < 60 {
+        format!("{}m", minutes)
+    } else if minutes < 24 * 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}d {}h", minutes / (24 * 60), (minutes / 60) % 24)
+    }
+}
+
+/// Format an instant as the time of day in `zone`, e.g. "09:30"
+pub fn format_time(at: DateTime<Utc>, zone: &Zone) -> String {
+    at.with_timezone(zone).format("%H:%M").to_string()
+}
+
+/// Format when a task was added and completed, in `zone`; pass the zone of
+/// the clock the task's day was worked out with
+pub fn format_task_times(task: &Task, zone: &Zone) -> String {
+    let added = format_time(task.created_at, zone);
+    match (task.completed_at, task.time_to_complete()) {
+        (Some(completed_at), Some(took)) => format!(
+            "added {}, done {}, took {}",
+            added,
+            format_time(completed_at, zone),
+            format_duration(took)
+        ),
+        _ => format!("added {}", added),
+    }
+}
+
+/// Parse a starting day given either as a date ("2026-10-01") or as a span
+/// back from `today` ("7d" for seven days ago, "2w" for two weeks ago)
+pub fn parse_since(spec: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let spec = spec.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(spec, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    
+    let days = if let Some(count) = spec.strip_suffix('d') {
+        count.parse::<i64>().ok()
+    } else if let Some(count) = spec.strip_suffix('w') {
+        count.parse::<i64>().ok().and_then(|weeks| weeks.checked_mul(7))
+    } else {
+        None
+    };
+    
+    let since = days
+        .filter(|&days| days >= 0)
+        .and_then(Duration::try_days)
+        .and_then(|span| today.checked_sub_signed(span));
+    match since {
+        Some(since) => Ok(since),
+        None => bail!("Invalid date or span '{}': use e.g. 7d, 2w or 2026-10-01", spec),
+    }
+}
+
+/// Parse a length of time such as "30m", "2h", "1h30m" or "1d"
+pub fn parse_duration(spec: &str) -> Result<Duration> {
+    let spec = spec.trim();
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    
+    for c in spec.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        
+        let count = number.parse::<i64>().ok();
+        number.clear();
+        let part = match (count, c) {
+            (Some(count), 'd') => Duration::try_days(count),
+            (Some(count), 'h') => Duration::try_hours(count),
+            (Some(count), 'm') => Duration::try_minutes(count),
+            _ => None,
+        };
+        total = match part.and_then(|part| total.checked_add(&part)) {
+            Some(total) => total,
+            None => bail!("Invalid duration '{}': use e.g. 30m, 2h or 1h30m", spec),
+        };
+    }
+    
+    if !number.is_empty() || total <= Duration::zero() {
+        bail!("Invalid duration '{}': use e.g. 30m, 2h or 1h30m", spec);
+    }
+    Ok(total)
+}
+
 /// Check if the daily goal is achieved
-pub fn is_daily_goal_achieved(completed_count: usize) -> bool {
-    completed_count >= DAILY_GOAL_COMPLETION_COUNT
+pub fn is_daily_goal_achieved(completed_count: usize, goal: usize) -> bool {
+    completed_count >= goal
+}
+
+/// Pluralize "task" for a count, e.g. "1 task" or "3 tasks"
+pub fn format_task_count(count: usize) -> String {
+    if count == 1 {
+        "1 task".to_string()
+    } else {
+        format!("{} tasks", count)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::env;
-    use tempfile::TempDir;
-
-    fn setup_test_env() -> TempDir {
-        let temp_dir = TempDir::new().unwrap();
-        env::set_var("XDG_DATA_HOME", temp_dir.path());
-        env::set_var("XDG_CONFIG_HOME", temp_dir.path());
-        temp_dir
-    }
 
     #[test]
     fn test_daily_goal_completion_count_constant() {
@@ -62,8 +157,6 @@ mod tests {
 
     #[test]
     fn test_get_data_dir() {
-        let _temp_dir = setup_test_env();
-        
         let result = get_data_dir();
         assert!(result.is_ok());
         
@@ -73,8 +166,6 @@ mod tests {
 
     #[test]
     fn test_get_config_dir() {
-        let _temp_dir = setup_test_env();
-        
         let result = get_config_dir();
         assert!(result.is_ok());
         
@@ -94,37 +185,119 @@ mod tests {
         assert_eq!(status, " ");
     }
 
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::seconds(30)), "<1m");
+        assert_eq!(format_duration(Duration::minutes(45)), "45m");
+        assert_eq!(format_duration(Duration::minutes(88)), "1h 28m");
+        assert_eq!(format_duration(Duration::hours(51)), "2d 3h");
+        assert_eq!(format_duration(Duration::minutes(-5)), "<1m");
+    }
+
+    #[test]
+    fn test_format_task_times() {
+        let now = Utc::now();
+        let mut task = Task::new(1, "Walk".to_string(), false, now.date_naive(), now);
+        assert!(format_task_times(&task, &Zone::Local).starts_with("added "));
+        assert!(!format_task_times(&task, &Zone::Local).contains("took"));
+        
+        task.completed = true;
+        task.completed_at = Some(now + Duration::minutes(90));
+        assert!(format_task_times(&task, &Zone::Local).ends_with("took 1h 30m"));
+    }
+
+    #[test]
+    fn test_format_task_times_in_zone() {
+        let created_at: DateTime<Utc> = "2026-10-16T22:30:00Z".parse().unwrap();
+        let mut task = Task::new(1, "Walk".to_string(), true, NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(), created_at);
+        task.completed_at = Some(created_at + Duration::minutes(45));
+        
+        let zone = Zone::Named(chrono_tz::Europe::Berlin);
+        assert_eq!(format_task_times(&task, &zone), "added 00:30, done 01:15, took 45m");
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        
+        assert_eq!(parse_since("7d", today).unwrap(), NaiveDate::from_ymd_opt(2026, 10, 9).unwrap());
+        assert_eq!(parse_since("2w", today).unwrap(), NaiveDate::from_ymd_opt(2026, 10, 2).unwrap());
+        assert_eq!(parse_since("0d", today).unwrap(), today);
+        assert_eq!(parse_since("2026-10-01", today).unwrap(), NaiveDate::from_ymd_opt(2026, 10, 1).unwrap());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::hours(2));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration(" 1d ").unwrap(), Duration::days(1));
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("2x").is_err());
+        assert!(parse_duration("-2h").is_err());
+        assert!(parse_duration("999999999999999d").is_err());
+        assert!(parse_duration("99999999999999999999m").is_err());
+    }
+
+    #[test]
+    fn test_parse_since_invalid() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        
+        assert!(parse_since("", today).is_err());
+        assert!(parse_since("7", today).is_err());
+        assert!(parse_since("-3d", today).is_err());
+        assert!(parse_since("soon", today).is_err());
+        assert!(parse_since("2026-13-01", today).is_err());
+        assert!(parse_since("99999999999d", today).is_err());
+        assert!(parse_since("9999999999999999w", today).is_err());
+        assert!(parse_since("9223372036854775807w", today).is_err());
+    }
+
     #[test]
     fn test_is_daily_goal_achieved_below_goal() {
-        assert!(!is_daily_goal_achieved(0));
-        assert!(!is_daily_goal_achieved(1));
-        assert!(!is_daily_goal_achieved(2));
+        assert!(!is_daily_goal_achieved(0, 3));
+        assert!(!is_daily_goal_achieved(1, 3));
+        assert!(!is_daily_goal_achieved(2, 3));
     }
 
     #[test]
     fn test_is_daily_goal_achieved_at_goal() {
-        assert!(is_daily_goal_achieved(3));
+        assert!(is_daily_goal_achieved(3, 3));
+        assert!(is_daily_goal_achieved(1, 1));
+        assert!(is_daily_goal_achieved(5, 5));
     }
 
     #[test]
     fn test_is_daily_goal_achieved_above_goal() {
-        assert!(is_daily_goal_achieved(4));
-        assert!(is_daily_goal_achieved(10));
-        assert!(is_daily_goal_achieved(100));
+        assert!(is_daily_goal_achieved(4, 3));
+        assert!(is_daily_goal_achieved(10, 3));
+        assert!(is_daily_goal_achieved(100, 3));
     }
 
     #[test]
     fn test_is_daily_goal_achieved_edge_cases() {
-        // Test with the constant directly
-        assert!(is_daily_goal_achieved(DAILY_GOAL_COMPLETION_COUNT));
-        assert!(!is_daily_goal_achieved(DAILY_GOAL_COMPLETION_COUNT - 1));
-        assert!(is_daily_goal_achieved(DAILY_GOAL_COMPLETION_COUNT + 1));
+        // Test with the default goal directly
+        assert!(is_daily_goal_achieved(DAILY_GOAL_COMPLETION_COUNT, DAILY_GOAL_COMPLETION_COUNT));
+        assert!(!is_daily_goal_achieved(DAILY_GOAL_COMPLETION_COUNT - 1, DAILY_GOAL_COMPLETION_COUNT));
+        assert!(is_daily_goal_achieved(DAILY_GOAL_COMPLETION_COUNT + 1, DAILY_GOAL_COMPLETION_COUNT));
+    }
+
+    #[test]
+    fn test_format_task_count() {
+        assert_eq!(format_task_count(0), "0 tasks");
+        assert_eq!(format_task_count(1), "1 task");
+        assert_eq!(format_task_count(5), "5 tasks");
     }
 
     #[test]
     fn test_directory_paths_are_different() {
-        let _temp_dir = setup_test_env();
-        
         let data_dir = get_data_dir().unwrap();
         let config_dir = get_config_dir().unwrap();
         
@@ -1,4 +1,4 @@
-use threeaday_core::{Database, Result, utils::*};
+use threeaday_core::{Database, EventSource, Result, utils::*};
 use gtk4::prelude::*;
 use gtk4::{
     Application, ApplicationWindow, Button, CheckButton, Entry, Label, Orientation,
@@ -6,6 +6,7 @@ use gtk4::{
 };
 use gtk4::Box as GtkBox;
 use std::cell::RefCell;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 const APP_ID: &str = "dev.threeaday.ThreeADay";
@@ -21,8 +22,12 @@ struct AppState {
 }
 
 impl AppState {
-    fn new(app: &Application) -> Result<Rc<RefCell<Self>>> {
-        let db = Database::new()?;
+    fn new(app: &Application, db_path: Option<&Path>) -> Result<Rc<RefCell<Self>>> {
+        let db = match db_path {
+            Some(path) => Database::open_configured(path)?,
+            None => Database::new()?,
+        }
+        .with_source(EventSource::Gui);
         let window = ApplicationWindow::builder()
             .application(app)
             .title("ThreeADay")
@@ -101,23 +106,33 @@ impl AppState {
                 // Update progress label
                 let completed_count = tasks.iter().filter(|t| t.completed).count();
                 let total_count = tasks.len();
+                let goal = state.db.config().goal_for(state.db.today());
+                let zone = state.db.clock().now().timezone();
                 
-                let progress_text = if is_daily_goal_achieved(completed_count) {
-                    format!("🎯 Daily goal achieved! ({} tasks completed)", completed_count)
+                let mut progress_text = if is_daily_goal_achieved(completed_count, goal) {
+                    format!("🎯 Daily goal achieved! ({} completed)", format_task_count(completed_count))
                 } else {
                     format!("Progress: {}/{} tasks completed", completed_count, total_count)
                 };
                 
+                match state.db.get_streak() {
+                    Ok(streak) if streak.current > 0 => {
+                        progress_text.push_str(&format!("\n🔥 {}-day streak", streak.current));
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Error calculating streak: {}", e),
+                }
+                
                 state.progress_label.set_text(&progress_text);
                 state.progress_label.remove_css_class("error-label");
                 state.progress_label.add_css_class("progress-label");
                 
                 // Show celebration if goal achieved
-                state.completed_revealer.set_reveal_child(is_daily_goal_achieved(completed_count));
+                state.completed_revealer.set_reveal_child(is_daily_goal_achieved(completed_count, goal));
                 
-                // Show only first 3 tasks (GUI enforces focus)
-                let display_tasks = tasks.iter().take(3);
-                let remaining_count = tasks.len().saturating_sub(3);
+                // Show only as many tasks as the daily goal (GUI enforces focus)
+                let display_tasks = tasks.iter().take(goal);
+                let remaining_count = tasks.len().saturating_sub(goal);
                 
                 for task in display_tasks {
                     let task_box = GtkBox::new(Orientation::Horizontal, 8);
@@ -126,7 +141,6 @@ impl AppState {
                     
                     let checkbox = CheckButton::new();
                     checkbox.set_active(task.completed);
-                    checkbox.set_sensitive(!task.completed);
                     task_box.append(&checkbox);
                     
                     let task_label = Label::new(Some(&task.text));
@@ -138,35 +152,93 @@ impl AppState {
                     }
                     
                     task_box.append(&task_label);
+                    
+                    // Inline editor, swapped in for the label while editing
+                    let edit_entry = Entry::new();
+                    edit_entry.set_text(&task.text);
+                    edit_entry.set_hexpand(true);
+                    edit_entry.set_visible(false);
+                    task_box.append(&edit_entry);
+                    
+                    // Show time-to-complete, with full timestamps on hover
+                    task_box.set_tooltip_text(Some(&format_task_times(task, &zone)));
+                    if let Some(took) = task.time_to_complete() {
+                        let took_label = Label::new(Some(&format_duration(took)));
+                        took_label.add_css_class("dim-label");
+                        task_box.append(&took_label);
+                    }
+                    
+                    let edit_button = Button::from_icon_name("document-edit-symbolic");
+                    edit_button.add_css_class("flat");
+                    edit_button.set_tooltip_text(Some("Edit task"));
+                    task_box.append(&edit_button);
+                    
+                    let delete_button = Button::from_icon_name("user-trash-symbolic");
+                    delete_button.add_css_class("flat");
+                    delete_button.set_tooltip_text(Some("Delete task"));
+                    task_box.append(&delete_button);
+                    
                     state.task_list.append(&task_box);
+                    
+                    edit_button.connect_clicked(glib::clone!(
+                        #[weak] task_label,
+                        #[weak] edit_entry,
+                        move |_| {
+                            task_label.set_visible(false);
+                            edit_entry.set_visible(true);
+                            edit_entry.grab_focus();
+                        }
+                    ));
+                    
+                    // Save the edit on Enter
+                    let task_id = task.id;
+                    let self_rc_weak = Rc::downgrade(self_rc);
+                    edit_entry.connect_activate(move |entry| {
+                        if let Some(strong_self_rc) = self_rc_weak.upgrade() {
+                            let text = entry.text().to_string();
+                            let result = strong_self_rc.borrow_mut().db.update_task_text(task_id, &text);
+                            // Rebuilding the list also puts the label back
+                            Self::refresh_tasks(&strong_self_rc);
+                            if let Err(e) = result {
+                                strong_self_rc.borrow().show_error(&format!("Error editing task {}: {}", task_id, e));
+                            }
+                        }
+                    });
+                    
+                    let self_rc_weak = Rc::downgrade(self_rc);
+                    delete_button.connect_clicked(move |_| {
+                        if let Some(strong_self_rc) = self_rc_weak.upgrade() {
+                            let result = strong_self_rc.borrow_mut().db.delete_task(task_id);
+                            Self::refresh_tasks(&strong_self_rc);
+                            if let Err(e) = result {
+                                strong_self_rc.borrow().show_error(&format!("Error deleting task {}: {}", task_id, e));
+                            }
+                        }
+                    });
 
-                    // Handle checkbox toggle
-                    if !task.completed {
-                        let task_id = task.id;
-                        let self_rc_weak = Rc::downgrade(self_rc);
-                        checkbox.connect_active_notify(move |cb| {
-                            if cb.is_active() {
-                                if let Some(strong_self_rc) = self_rc_weak.upgrade() {
-                                    let mut current_app_state = strong_self_rc.borrow_mut();
-                                    match current_app_state.db.complete_task(task_id) {
-                                        Ok(true) => {
-                                            drop(current_app_state);
-                                            Self::refresh_tasks(&strong_self_rc);
-                                        }
-                                        Ok(false) => {
-                                            // Task was already complete or not found
-                                        }
-                                        Err(e) => {
-                                            eprintln!("Error completing task {}: {}", task_id, e);
-                                        }
-                                    }
-                                }
+                    // Handle checkbox toggle in both directions so a mis-click can be undone
+                    let self_rc_weak = Rc::downgrade(self_rc);
+                    checkbox.connect_active_notify(move |cb| {
+                        if let Some(strong_self_rc) = self_rc_weak.upgrade() {
+                            let mut current_app_state = strong_self_rc.borrow_mut();
+                            let result = if cb.is_active() {
+                                current_app_state.db.complete_task(task_id)
+                            } else {
+                                current_app_state.db.uncomplete_task(task_id)
+                            };
+                            drop(current_app_state);
+                            
+                            // Redraw even if nothing changed, e.g. because the task was
+                            // changed elsewhere, so the checkbox shows what is stored
+                            Self::refresh_tasks(&strong_self_rc);
+                            if let Err(e) = result {
+                                strong_self_rc.borrow().show_error(&format!("Error updating task {}: {}", task_id, e));
                             }
-                        });
-                    }
+                        }
+                    });
                 }
                 
-                // Show note if there are more tasks beyond the 3 displayed
+                // Show note if there are more tasks beyond the ones displayed
                 if remaining_count > 0 {
                     let more_label = Label::new(Some(&format!("... and {} more task(s) (use CLI to see all)", remaining_count)));
                     more_label.add_css_class("dim-label");
@@ -197,10 +269,13 @@ impl AppState {
         
         let mut state = self_rc.borrow_mut();
         
-        // Check if we already have 3 tasks (GUI enforces focus)
-        let tasks = state.db.get_today_tasks()?;
-        if tasks.len() >= DAILY_GOAL_COMPLETION_COUNT {
-            state.progress_label.set_text("Focus on your 3 tasks first! Complete some before adding more.");
+        // Check if we already have as many tasks as the daily goal (GUI enforces focus)
+        let summary = state.db.get_today_summary()?;
+        if summary.total >= summary.goal {
+            state.progress_label.set_text(&format!(
+                "Focus on your {} first! Complete some before adding more.",
+                format_task_count(summary.goal)
+            ));
             state.progress_label.remove_css_class("progress-label");
             state.progress_label.add_css_class("error-label");
             return Ok(());
@@ -216,11 +291,15 @@ impl AppState {
     }
 
     fn handle_add_task_error(&self, error: &anyhow::Error) {
-        let error_message = format!("Error adding task: {}", error);
-        self.progress_label.set_text(&error_message);
+        self.show_error(&format!("Error adding task: {}", error));
+    }
+
+    /// Show an error in place of the progress text until the next refresh
+    fn show_error(&self, message: &str) {
+        self.progress_label.set_text(message);
         self.progress_label.remove_css_class("progress-label");
         self.progress_label.add_css_class("error-label");
-        eprintln!("{}", error_message);
+        eprintln!("{}", message);
     }
 }
 
@@ -321,7 +400,9 @@ fn setup_callbacks(state: &Rc<RefCell<AppState>>) {
     state.borrow().window.add_controller(key_controller);
 }
 
-pub fn run_gui() -> Result<()> {
+/// Run the GUI on the database at `db_path`, or the default one. `args` are
+/// passed on to GTK.
+pub fn run_gui(db_path: Option<PathBuf>, args: &[String]) -> Result<()> {
     let app = Application::builder()
         .application_id(APP_ID)
         .build();
@@ -329,7 +410,7 @@ pub fn run_gui() -> Result<()> {
     app.connect_activate(move |app| {
         setup_css();
         
-        match AppState::new(app) {
+        match AppState::new(app, db_path.as_deref()) {
             Ok(state) => {
                 setup_callbacks(&state);
                 AppState::refresh_tasks(&state);
@@ -345,6 +426,6 @@ pub fn run_gui() -> Result<()> {
         }
     });
 
-    app.run();
+    app.run_with_args(args);
     Ok(())
 }
\ No newline at end of file
@@ -1,8 +1,22 @@
 mod gui;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use gui::run_gui;
+use std::path::PathBuf;
 
 fn main() -> Result<()> {
-    run_gui()
-}
\ No newline at end of file
+    let mut args: Vec<String> = std::env::args().collect();
+
+    // --db <path> opens another database than the default one; GTK gets the
+    // remaining arguments
+    let db_path = match args.iter().position(|arg| arg == "--db") {
+        Some(index) => {
+            let path = args.get(index + 1).map(PathBuf::from).context("--db needs a path")?;
+            args.drain(index..index + 2);
+            Some(path)
+        }
+        None => None,
+    };
+
+    run_gui(db_path, &args)
+}
@@ -0,0 +1,183 @@
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use threeaday_core::ipc::{Request, Response, Signal};
+use threeaday_core::{Context, Result};
+use tokio::io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+/// A request from a client, with where to send the answer
+pub type IpcRequest = (Request, oneshot::Sender<Response>);
+
+/// Listen on the service socket at `path` in the background.
+///
+/// Requests are passed to the main loop through `requests`; subscribers get
+/// everything sent on `signals`. A socket left behind by a service that
+/// crashed is replaced, but one that still answers is not.
+pub fn listen(
+    path: &Path,
+    requests: mpsc::UnboundedSender<IpcRequest>,
+    signals: broadcast::Sender<Signal>,
+) -> Result<()> {
+    if path.exists() {
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            anyhow::bail!("Another service is already listening on {}", path.display());
+        }
+        std::fs::remove_file(path)?;
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("Failed to listen on {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve(stream, requests.clone(), signals.clone()));
+                }
+                Err(e) => eprintln!("Error accepting socket connection: {}", e),
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Answer the requests of one client until it disconnects
+async fn serve(stream: UnixStream, requests: mpsc::UnboundedSender<IpcRequest>, signals: broadcast::Sender<Signal>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(Request::Subscribe) => {
+                let receiver = signals.subscribe();
+                if write_line(&mut writer, &Response::Ok).await.is_ok() {
+                    forward_signals(&mut writer, receiver).await;
+                }
+                return;
+            }
+            Ok(request) => {
+                let (reply_tx, reply_rx) = oneshot::channel();
+                if requests.send((request, reply_tx)).is_err() {
+                    return;
+                }
+                match reply_rx.await {
+                    Ok(response) => response,
+                    Err(_) => return,
+                }
+            }
+            Err(e) => Response::Error { message: format!("Invalid request: {}", e) },
+        };
+
+        if write_line(&mut writer, &response).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Write every signal to a subscriber until it disconnects
+async fn forward_signals(writer: &mut (impl AsyncWrite + Unpin), mut signals: broadcast::Receiver<Signal>) {
+    loop {
+        match signals.recv().await {
+            Ok(signal) => {
+                if write_line(writer, &signal).await.is_err() {
+                    return;
+                }
+            }
+            // A slow subscriber misses some signals but stays connected
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+async fn write_line(writer: &mut (impl AsyncWrite + Unpin), value: &impl serde::Serialize) -> Result<()> {
+    let mut line = serde_json::to_string(value)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use threeaday_core::ipc::request_at;
+
+    /// Answer every request with `Response::Ok` on a runtime thread
+    fn echo_service(path: &Path) -> broadcast::Sender<Signal> {
+        let (request_tx, mut request_rx) = mpsc::unbounded_channel::<IpcRequest>();
+        let (signals, _) = broadcast::channel(4);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        runtime.block_on(async { listen(path, request_tx, signals.clone()) }).unwrap();
+        std::thread::spawn(move || {
+            runtime.block_on(async {
+                while let Some((_, reply)) = request_rx.recv().await {
+                    let _ = reply.send(Response::Ok);
+                }
+            })
+        });
+        signals
+    }
+
+    #[test]
+    fn test_requests_reach_main_loop() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("service.sock");
+        echo_service(&path);
+
+        assert_eq!(request_at(&path, &Request::ReloadConfig).unwrap(), Response::Ok);
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_invalid_request() {
+        use std::io::{BufRead, Write};
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("service.sock");
+        echo_service(&path);
+
+        let mut stream = std::os::unix::net::UnixStream::connect(&path).unwrap();
+        writeln!(stream, r#"{{"method":"Explode"}}"#).unwrap();
+        let mut line = String::new();
+        std::io::BufReader::new(&stream).read_line(&mut line).unwrap();
+
+        let response: Response = serde_json::from_str(&line).unwrap();
+        assert!(matches!(response, Response::Error { message } if message.contains("Invalid request")));
+    }
+
+    #[test]
+    fn test_subscribers_receive_signals() {
+        use std::io::{BufRead, Write};
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("service.sock");
+        let signals = echo_service(&path);
+
+        let mut stream = std::os::unix::net::UnixStream::connect(&path).unwrap();
+        writeln!(stream, r#"{{"method":"Subscribe"}}"#).unwrap();
+        let mut lines = std::io::BufReader::new(&stream).lines();
+        assert_eq!(lines.next().unwrap().unwrap(), r#"{"result":"ok"}"#);
+
+        signals.send(Signal::GoalAchieved { completed: 3 }).unwrap();
+        assert_eq!(lines.next().unwrap().unwrap(), r#"{"signal":"GoalAchieved","completed":3}"#);
+    }
+
+    #[test]
+    fn test_refuses_to_replace_live_socket() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("service.sock");
+        echo_service(&path);
+
+        let (request_tx, _) = mpsc::unbounded_channel();
+        let error = listen(&path, request_tx, broadcast::channel(1).0).unwrap_err();
+        assert!(error.to_string().contains("Another service"));
+    }
+}
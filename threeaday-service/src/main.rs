@@ -1,9 +1,24 @@
+mod ipc;
+mod notice;
+mod notifier;
+mod scheduler;
 mod service;
+mod watcher;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
 use service::run_service;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    run_service().await
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // --headless prints notifications to stdout instead of the desktop
+    let headless = args.iter().any(|arg| arg == "--headless");
+    // --db <path> uses another database than the default one
+    let db_path = args
+        .iter()
+        .position(|arg| arg == "--db")
+        .map(|index| args.get(index + 1).map(PathBuf::from).context("--db needs a path"))
+        .transpose()?;
+    run_service(headless, db_path).await
 }
\ No newline at end of file
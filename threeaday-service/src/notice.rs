@@ -0,0 +1,201 @@
+use threeaday_core::{DaySummary, utils::*};
+
+/// How long the "Snooze" button pauses reminders
+pub const SNOOZE_MINUTES: i64 = 30;
+
+/// Open tasks that get their own "Done" button
+const MAX_TASK_ACTIONS: usize = 2;
+
+/// Longest task text shown on a "Done" button
+const MAX_ACTION_LABEL_CHARS: usize = 24;
+
+/// A notification the service has decided to show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Notice {
+    Startup,
+    /// The morning greeting; tasks moved to the new day are already open
+    Reset { goal: usize, streak: u32, open_tasks: Vec<(i64, String)> },
+    Reminder { summary: DaySummary, open_tasks: Vec<(i64, String)> },
+    Achievement { completed: usize },
+    /// The edited config file couldn't be applied
+    ConfigError { error: String },
+}
+
+/// A button on a notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoticeAction {
+    /// Open the GUI
+    Open,
+    /// Pause reminders for `SNOOZE_MINUTES`
+    Snooze,
+    /// Complete the task with this ID
+    Done(i64),
+}
+
+impl NoticeAction {
+    /// Identifier sent to the notification daemon and back in the callback
+    pub fn id(&self) -> String {
+        match self {
+            NoticeAction::Open => "open".to_string(),
+            NoticeAction::Snooze => "snooze".to_string(),
+            NoticeAction::Done(id) => format!("done:{}", id),
+        }
+    }
+
+    /// Parse an action identifier. Clicking the notification itself sends
+    /// "default", which opens the GUI as well.
+    pub fn parse(id: &str) -> Option<Self> {
+        match id {
+            "open" | "default" => Some(NoticeAction::Open),
+            "snooze" => Some(NoticeAction::Snooze),
+            _ => id.strip_prefix("done:")?.parse().ok().map(NoticeAction::Done),
+        }
+    }
+}
+
+impl Notice {
+    pub fn summary(&self) -> &'static str {
+        match self {
+            Notice::Startup => "ThreeADay Service",
+            Notice::Reset { .. } => "ThreeADay - Fresh Start",
+            Notice::Reminder { .. } => "ThreeADay Reminder",
+            Notice::Achievement { .. } => "ThreeADay - Goal Achieved!",
+            Notice::ConfigError { .. } => "ThreeADay - Config Error",
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match self {
+            Notice::Startup => "Service started - reminders and daily resets are now active! 🚀".to_string(),
+            Notice::Reset { goal, streak, .. } => {
+                let mut message = format!(
+                    "🌅 Good morning! It's a fresh start. What {} will you complete today?",
+                    format_task_count(*goal)
+                );
+                if *streak > 0 {
+                    message.push_str(&format!("\n🔥 You're on a {}-day streak, keep it going!", streak));
+                }
+                message
+            }
+            Notice::Reminder { summary, .. } if summary.total == 0 => {
+                "Time to add your first task for today! 🎯".to_string()
+            }
+            Notice::Reminder { summary, .. } => format!(
+                "You have {}/{} tasks completed. {} more to reach your daily goal! 💪",
+                summary.completed, summary.total, summary.remaining()
+            ),
+            Notice::Achievement { completed } => format!(
+                "🎉 Congratulations! You've completed {} today. Goal achieved! 🎯",
+                format_task_count(*completed)
+            ),
+            Notice::ConfigError { error } => format!(
+                "Couldn't apply config.toml, keeping the previous settings: {}",
+                error
+            ),
+        }
+    }
+
+    /// How long the popup stays up, in milliseconds
+    pub fn timeout(&self) -> i32 {
+        match self {
+            Notice::Startup => 3000,
+            Notice::Reminder { .. } => 5000,
+            Notice::Reset { .. } | Notice::Achievement { .. } | Notice::ConfigError { .. } => 8000,
+        }
+    }
+
+    /// Buttons offered on the notification, with their labels
+    pub fn actions(&self) -> Vec<(NoticeAction, String)> {
+        let mut actions = Vec::new();
+        match self {
+            Notice::Reminder { open_tasks, .. } | Notice::Reset { open_tasks, .. } => {
+                for (id, text) in open_tasks.iter().take(MAX_TASK_ACTIONS) {
+                    actions.push((NoticeAction::Done(*id), format!("Done: {}", action_label(text))));
+                }
+            }
+            Notice::Startup | Notice::Achievement { .. } | Notice::ConfigError { .. } => return actions,
+        }
+        actions.push((NoticeAction::Snooze, format!("Snooze {}m", SNOOZE_MINUTES)));
+        actions.push((NoticeAction::Open, "Open".to_string()));
+        actions
+    }
+}
+
+/// Shorten a task text to fit on a button
+fn action_label(text: &str) -> String {
+    if text.chars().count() <= MAX_ACTION_LABEL_CHARS {
+        text.to_string()
+    } else {
+        let short: String = text.chars().take(MAX_ACTION_LABEL_CHARS - 1).collect();
+        format!("{}…", short.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn summary(completed: usize, total: usize) -> DaySummary {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        DaySummary { day, completed, total, goal: 3 }
+    }
+
+    #[test]
+    fn test_reset_notice_mentions_streak() {
+        let notice = Notice::Reset { goal: 1, streak: 4, open_tasks: Vec::new() };
+
+        assert!(notice.body().contains("What 1 task will you complete today?"));
+        assert!(notice.body().contains("4-day streak"));
+        assert!(!Notice::Reset { goal: 3, streak: 0, open_tasks: Vec::new() }.body().contains("streak"));
+    }
+
+    #[test]
+    fn test_reminder_notice_body() {
+        let empty = Notice::Reminder { summary: summary(0, 0), open_tasks: Vec::new() };
+        let started = Notice::Reminder { summary: summary(1, 2), open_tasks: Vec::new() };
+
+        assert_eq!(empty.body(), "Time to add your first task for today! 🎯");
+        assert_eq!(started.body(), "You have 1/2 tasks completed. 2 more to reach your daily goal! 💪");
+    }
+
+    #[test]
+    fn test_reminder_actions() {
+        let notice = Notice::Reminder {
+            summary: summary(0, 3),
+            open_tasks: vec![
+                (4, "Walk".to_string()),
+                (5, "Reply to the email from the landlord about the heating".to_string()),
+                (6, "Dishes".to_string()),
+            ],
+        };
+
+        assert_eq!(notice.actions(), vec![
+            (NoticeAction::Done(4), "Done: Walk".to_string()),
+            (NoticeAction::Done(5), "Done: Reply to the email from…".to_string()),
+            (NoticeAction::Snooze, "Snooze 30m".to_string()),
+            (NoticeAction::Open, "Open".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_other_notice_actions() {
+        let reset = Notice::Reset { goal: 3, streak: 0, open_tasks: Vec::new() };
+        assert_eq!(reset.actions().len(), 2);
+        let reset = Notice::Reset { goal: 3, streak: 0, open_tasks: vec![(7, "Call the dentist".to_string())] };
+        assert_eq!(reset.actions()[0], (NoticeAction::Done(7), "Done: Call the dentist".to_string()));
+        assert_eq!(reset.actions().len(), 3);
+        assert!(Notice::Startup.actions().is_empty());
+        assert!(Notice::Achievement { completed: 3 }.actions().is_empty());
+    }
+
+    #[test]
+    fn test_action_ids_roundtrip() {
+        for action in [NoticeAction::Open, NoticeAction::Snooze, NoticeAction::Done(42)] {
+            assert_eq!(NoticeAction::parse(&action.id()), Some(action));
+        }
+        assert_eq!(NoticeAction::parse("default"), Some(NoticeAction::Open));
+        assert_eq!(NoticeAction::parse("__closed"), None);
+        assert_eq!(NoticeAction::parse("done:soon"), None);
+    }
+}
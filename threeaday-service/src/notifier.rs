@@ -0,0 +1,116 @@
+use crate::notice::{Notice, NoticeAction};
+use notify_rust::Notification;
+use threeaday_core::Result;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Where the service sends its notifications
+pub trait Notifier: Send {
+    fn notify(&self, notice: &Notice) -> Result<()>;
+}
+
+/// Desktop notifications over D-Bus, with clickable buttons
+pub struct DesktopNotifier {
+    actions: UnboundedSender<NoticeAction>,
+}
+
+impl DesktopNotifier {
+    /// Clicked buttons are sent to `actions`
+    pub fn new(actions: UnboundedSender<NoticeAction>) -> Self {
+        DesktopNotifier { actions }
+    }
+}
+
+impl Notifier for DesktopNotifier {
+    fn notify(&self, notice: &Notice) -> Result<()> {
+        let mut notification = Notification::new();
+        notification
+            .summary(notice.summary())
+            .body(&notice.body())
+            .timeout(notice.timeout());
+        let buttons = notice.actions();
+        for (action, label) in &buttons {
+            notification.action(&action.id(), label);
+        }
+
+        let handle = notification.show()?;
+        if !buttons.is_empty() {
+            // Waiting for the click blocks until the popup is closed
+            let actions = self.actions.clone();
+            std::thread::spawn(move || {
+                handle.wait_for_action(|id| {
+                    if let Some(action) = NoticeAction::parse(id) {
+                        let _ = actions.send(action);
+                    }
+                });
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Prints notifications to stdout, for running without a desktop session
+pub struct LogNotifier;
+
+impl LogNotifier {
+    fn format(notice: &Notice) -> String {
+        format!("[{}] {}", notice.summary(), notice.body().replace('\n', " "))
+    }
+}
+
+impl Notifier for LogNotifier {
+    fn notify(&self, notice: &Notice) -> Result<()> {
+        println!("{}", Self::format(notice));
+        Ok(())
+    }
+}
+
+/// Keeps every notification in memory so tests can check them
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+pub struct RecordingNotifier {
+    notices: std::sync::Arc<std::sync::Mutex<Vec<Notice>>>,
+}
+
+#[cfg(test)]
+impl RecordingNotifier {
+    /// Get the notices recorded since the last call
+    pub fn take(&self) -> Vec<Notice> {
+        std::mem::take(&mut *self.notices.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+impl Notifier for RecordingNotifier {
+    fn notify(&self, notice: &Notice) -> Result<()> {
+        self.notices.lock().unwrap().push(notice.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_notifier_format() {
+        let notice = Notice::Reset { goal: 3, streak: 2, open_tasks: Vec::new() };
+
+        assert_eq!(
+            LogNotifier::format(&notice),
+            "[ThreeADay - Fresh Start] 🌅 Good morning! It's a fresh start. What 3 tasks will you complete today? \
+             🔥 You're on a 2-day streak, keep it going!"
+        );
+    }
+
+    #[test]
+    fn test_recording_notifier_take() {
+        let notifier = RecordingNotifier::default();
+        let boxed: Box<dyn Notifier> = Box::new(notifier.clone());
+
+        boxed.notify(&Notice::Startup).unwrap();
+        boxed.notify(&Notice::Achievement { completed: 3 }).unwrap();
+
+        assert_eq!(notifier.take(), vec![Notice::Startup, Notice::Achievement { completed: 3 }]);
+        assert!(notifier.take().is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveTime, TimeZone};
+use threeaday_core::day::logical_date;
+
+/// How far past a DST gap we look for the first valid local time
+const MAX_DST_GAP_MINUTES: i64 = 3 * 60;
+
+/// Decides when the daily reset happens.
+///
+/// The scheduler works on wall-clock time in the user's timezone rather than
+/// on a monotonic timer: every call to [`ResetScheduler::poll`] derives the
+/// logical day from the current time, so a reset missed while the laptop was
+/// suspended fires as soon as the service wakes up again.
+#[derive(Debug, Clone)]
+pub struct ResetScheduler {
+    reset_time: NaiveTime,
+    last_fired: Option<NaiveDate>,
+}
+
+impl ResetScheduler {
+    /// Create a scheduler; `last_fired` is the last logical day whose reset
+    /// has already been handled, so it doesn't fire again after a restart
+    pub fn new(reset_time: NaiveTime, last_fired: Option<NaiveDate>) -> Self {
+        ResetScheduler { reset_time, last_fired }
+    }
+
+    /// Get the logical day `now` belongs to
+    pub fn logical_day<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> NaiveDate {
+        logical_date(now.naive_local(), self.reset_time)
+    }
+
+    /// Get the instant the logical day `day` starts at.
+    ///
+    /// When the reset time falls into a DST gap the day starts as soon as the
+    /// clocks have jumped forward; when it occurs twice the first one counts.
+    pub fn day_start<Tz: TimeZone>(&self, day: NaiveDate, tz: &Tz) -> DateTime<Tz> {
+        let start = day.and_time(self.reset_time);
+        for minutes in 0..=MAX_DST_GAP_MINUTES {
+            match tz.from_local_datetime(&(start + Duration::minutes(minutes))) {
+                LocalResult::Single(instant) => return instant,
+                LocalResult::Ambiguous(earliest, _) => return earliest,
+                LocalResult::None => continue,
+            }
+        }
+        // No real timezone has a gap this long; fall back to the UTC reading
+        tz.from_utc_datetime(&start)
+    }
+
+    /// Get the instant of the next reset after `now`
+    pub fn next_reset<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> DateTime<Tz> {
+        let today = self.logical_day(now);
+        let tomorrow = today.succ_opt().unwrap_or(today);
+        self.day_start(tomorrow, &now.timezone())
+    }
+
+    /// Get the last logical day whose reset has fired
+    pub fn last_fired(&self) -> Option<NaiveDate> {
+        self.last_fired
+    }
+
+    /// Get the logical day whose reset can fire at `now`: the current one,
+    /// once the wall clock has passed its reset on the same date. In the hours
+    /// before the reset the logical day is still the previous one, whose reset
+    /// is long past, so nothing fires until the next day begins.
+    fn resettable_day<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<NaiveDate> {
+        let today = self.logical_day(now);
+        (now.naive_local().date() == today).then_some(today)
+    }
+
+    /// Check whether the current logical day's reset hasn't fired yet
+    pub fn is_due<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> bool {
+        self.resettable_day(now)
+            .is_some_and(|today| self.last_fired.is_none_or(|last| last < today))
+    }
+
+    /// Check whether a new logical day has started since the last reset.
+    ///
+    /// Returns the new day at most once; a day earlier than the last one that
+    /// fired (the clock was set back, or a DST fall-back repeats the reset
+    /// hour) never fires again.
+    pub fn poll<Tz: TimeZone>(&mut self, now: &DateTime<Tz>) -> Option<NaiveDate> {
+        let today = self.resettable_day(now)?;
+        if self.last_fired.is_some_and(|last| last >= today) {
+            return None;
+        }
+        self.last_fired = Some(today);
+        Some(today)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+    use chrono_tz::America::New_York;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn time(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").unwrap()
+    }
+
+    fn local<Tz: TimeZone>(tz: &Tz, s: &str) -> DateTime<Tz> {
+        let naive = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        tz.from_local_datetime(&naive).earliest().unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        local(&Utc, s)
+    }
+
+    #[test]
+    fn test_poll_fires_after_reset_minute() {
+        // The old hour/minute check never fired at 07:05 for a 06:30 reset
+        let mut scheduler = ResetScheduler::new(time("06:30"), Some(date("2026-10-15")));
+
+        assert_eq!(scheduler.poll(&utc("2026-10-16 06:29")), None);
+        assert_eq!(scheduler.poll(&utc("2026-10-16 07:05")), Some(date("2026-10-16")));
+    }
+
+    #[test]
+    fn test_poll_fires_once_per_day() {
+        let mut scheduler = ResetScheduler::new(time("06:00"), None);
+
+        assert_eq!(scheduler.poll(&utc("2026-10-16 06:00")), Some(date("2026-10-16")));
+        assert_eq!(scheduler.poll(&utc("2026-10-16 07:00")), None);
+        assert_eq!(scheduler.poll(&utc("2026-10-16 23:59")), None);
+        assert_eq!(scheduler.poll(&utc("2026-10-17 05:59")), None);
+        assert_eq!(scheduler.poll(&utc("2026-10-17 06:00")), Some(date("2026-10-17")));
+    }
+
+    #[test]
+    fn test_poll_waits_for_reset_when_started_before_it() {
+        // At 05:00 the logical day is still the 15th, whose reset was a day ago
+        let mut scheduler = ResetScheduler::new(time("06:00"), None);
+
+        assert_eq!(scheduler.poll(&utc("2026-10-16 05:00")), None);
+        assert!(!scheduler.is_due(&utc("2026-10-16 05:00")));
+        assert_eq!(scheduler.poll(&utc("2026-10-16 06:00")), Some(date("2026-10-16")));
+    }
+
+    #[test]
+    fn test_poll_respects_last_fired_after_restart() {
+        let mut scheduler = ResetScheduler::new(time("06:00"), Some(date("2026-10-16")));
+        assert_eq!(scheduler.poll(&utc("2026-10-16 12:00")), None);
+    }
+
+    #[test]
+    fn test_poll_catches_up_after_suspend() {
+        let mut scheduler = ResetScheduler::new(time("06:00"), Some(date("2026-10-14")));
+
+        // Suspended over two resets: the current day fires once on resume
+        assert_eq!(scheduler.poll(&utc("2026-10-16 09:15")), Some(date("2026-10-16")));
+        assert_eq!(scheduler.poll(&utc("2026-10-16 09:16")), None);
+    }
+
+    #[test]
+    fn test_poll_ignores_clock_set_back() {
+        let mut scheduler = ResetScheduler::new(time("06:00"), None);
+
+        assert_eq!(scheduler.poll(&utc("2026-10-16 06:10")), Some(date("2026-10-16")));
+        assert_eq!(scheduler.poll(&utc("2026-10-16 05:50")), None);
+        assert_eq!(scheduler.poll(&utc("2026-10-16 06:10")), None);
+    }
+
+    #[test]
+    fn test_poll_uses_local_time() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        let mut scheduler = ResetScheduler::new(time("06:00"), Some(date("2026-10-15")));
+
+        // 05:30 UTC is already 07:30 in UTC+2
+        let now = utc("2026-10-16 05:30").with_timezone(&tz);
+        assert_eq!(scheduler.poll(&now), Some(date("2026-10-16")));
+    }
+
+    #[test]
+    fn test_next_reset() {
+        let scheduler = ResetScheduler::new(time("06:30"), None);
+
+        assert_eq!(scheduler.next_reset(&utc("2026-10-16 05:00")), utc("2026-10-16 06:30"));
+        assert_eq!(scheduler.next_reset(&utc("2026-10-16 06:30")), utc("2026-10-17 06:30"));
+        assert_eq!(scheduler.next_reset(&utc("2026-10-16 23:00")), utc("2026-10-17 06:30"));
+    }
+
+    #[test]
+    fn test_next_reset_across_spring_forward() {
+        // 2026-03-08 02:30 does not exist in New York; clocks jump to 03:00
+        let scheduler = ResetScheduler::new(time("02:30"), None);
+        let now = local(&New_York, "2026-03-07 12:00");
+
+        let next = scheduler.next_reset(&now);
+        assert_eq!(next, local(&New_York, "2026-03-08 03:00"));
+        assert_eq!(next - now, Duration::hours(14));
+    }
+
+    #[test]
+    fn test_next_reset_across_fall_back() {
+        // 2026-11-01 01:30 happens twice in New York; the first one counts
+        let scheduler = ResetScheduler::new(time("01:30"), None);
+        let now = local(&New_York, "2026-10-31 12:00");
+
+        let next = scheduler.next_reset(&now);
+        assert_eq!(next.naive_local(), local(&New_York, "2026-11-01 01:30").naive_local());
+        assert_eq!(next - now, Duration::minutes(13 * 60 + 30));
+    }
+
+    #[test]
+    fn test_poll_across_fall_back_fires_once() {
+        let mut scheduler = ResetScheduler::new(time("01:30"), Some(date("2026-10-31")));
+        let first = local(&New_York, "2026-11-01 01:30");
+
+        assert_eq!(scheduler.poll(&first), Some(date("2026-11-01")));
+        // An hour later the wall clock reads 01:00 again, then 01:30 again
+        assert_eq!(scheduler.poll(&(first + Duration::minutes(30))), None);
+        assert_eq!(scheduler.poll(&(first + Duration::hours(1))), None);
+    }
+
+    #[test]
+    fn test_is_due() {
+        let mut scheduler = ResetScheduler::new(time("06:00"), Some(date("2026-10-15")));
+
+        assert!(!scheduler.is_due(&utc("2026-10-16 05:59")));
+        assert!(scheduler.is_due(&utc("2026-10-16 06:00")));
+        scheduler.poll(&utc("2026-10-16 06:00"));
+        assert!(!scheduler.is_due(&utc("2026-10-16 06:01")));
+    }
+}
@@ -1,43 +1,172 @@
-use threeaday_core::{Database, Config, Result, utils::*};
-use notify_rust::Notification;
+use crate::ipc::{self, IpcRequest};
+use crate::notice::{Notice, NoticeAction, SNOOZE_MINUTES};
+use crate::notifier::{DesktopNotifier, LogNotifier, Notifier};
+use crate::scheduler::ResetScheduler;
+use crate::watcher::{watch_config, watch_database};
+use threeaday_core::ipc::{socket_path, Request, Response, ServiceStatus, Signal};
+use threeaday_core::{Clock, Database, Config, EventSource, NaiveDate, ReminderPause, Result, ServiceDayState, Zone};
+use chrono::{DateTime, TimeZone, Utc};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::{sleep, Instant};
-use chrono::Timelike;
+use tokio::sync::broadcast;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::time::sleep;
 
-struct ServiceState {
+/// Longest time the main loop sleeps before looking at the clock again, so
+/// a suspend or a changed clock is noticed even when nothing is scheduled
+const MAX_SLEEP: Duration = Duration::from_secs(15 * 60);
+
+/// Shortest sleep, so something that is due but can't be sent doesn't spin
+/// the loop
+const MIN_SLEEP: Duration = Duration::from_secs(1);
+
+/// How long to wait for an editor to finish saving the config file
+const CONFIG_SETTLE: Duration = Duration::from_millis(200);
+
+/// Signals kept for subscribers that fall behind
+const SIGNAL_BUFFER: usize = 16;
+
+pub struct ServiceState {
     db: Database,
     config: Config,
-    last_reminder_time: Option<Instant>,
-    last_reset_time: Option<Instant>,
-    reminders_sent_today: u32,
+    clock: Arc<dyn Clock>,
+    /// What has been sent on the current logical day, persisted across restarts
+    day_state: ServiceDayState,
+    scheduler: ResetScheduler,
+    started_at: DateTime<Utc>,
+    notifier: Box<dyn Notifier>,
+    signals: broadcast::Sender<Signal>,
+    /// Completed tasks seen in the last round, to notice new completions
+    completed: Option<(NaiveDate, usize)>,
+    /// Config file to reload, `None` for the default one
+    config_path: Option<PathBuf>,
 }
 
 impl ServiceState {
-    fn new() -> Result<Self> {
-        let db = Database::new()?;
-        let config = Config::load()?;
-        
+    /// Create the service state, taking the time from the database's clock
+    pub fn new(mut db: Database, config: Config, notifier: Box<dyn Notifier>) -> Result<Self> {
+        // Days and goals follow the service's config
+        db.set_config(config.clone());
+        db.set_source(EventSource::Service);
+        let clock = db.clock().clone();
+        let day_state = db.get_service_state(db.today())?;
+        let last_reset = day_state.reset_notified.then_some(day_state.day);
+        let scheduler = ResetScheduler::new(config.reset_time(), last_reset);
+        let started_at = clock.utc_now();
+        let (signals, _) = broadcast::channel(SIGNAL_BUFFER);
+
         Ok(ServiceState {
             db,
             config,
-            last_reminder_time: None,
-            last_reset_time: None,
-            reminders_sent_today: 0,
+            clock,
+            day_state,
+            scheduler,
+            started_at,
+            notifier,
+            signals,
+            completed: None,
+            config_path: None,
+        })
+    }
+
+    /// Reload the config from `path` instead of the default config file
+    pub fn with_config_path(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    fn config_path(&self) -> Result<PathBuf> {
+        match &self.config_path {
+            Some(path) => Ok(path.clone()),
+            None => Config::get_config_path(),
+        }
+    }
+
+    /// Switch to the persisted state of the new logical day once the day rolls over
+    fn refresh_day_state(&mut self) -> Result<()> {
+        let today = self.db.today();
+        if self.day_state.day != today {
+            self.day_state = self.db.get_service_state(today)?;
+        }
+        Ok(())
+    }
+
+    fn save_day_state(&mut self) -> Result<()> {
+        self.db.save_service_state(&self.day_state)
+    }
+
+    fn reminder_pause(&self) -> ReminderPause {
+        self.db.get_reminder_pause().unwrap_or_else(|e| {
+            eprintln!("Error reading snooze state: {}", e);
+            ReminderPause::default()
         })
     }
 
+    /// Check for quiet hours, a snooze or do-not-disturb
+    fn reminders_paused(&self) -> bool {
+        let now = self.clock.now();
+        self.config.is_quiet_at(now.naive_local()) || self.reminder_pause().is_active(now.with_timezone(&Utc))
+    }
+
+    /// Get when a snooze and quiet hours that hold back notifications at `at`
+    /// are over, or `None` while do-not-disturb is on
+    fn pause_end(&self, at: DateTime<Zone>) -> Option<DateTime<Zone>> {
+        let pause = self.reminder_pause();
+        if pause.dnd {
+            return None;
+        }
+
+        let mut end = at;
+        if let Some(snoozed_until) = pause.snoozed_until {
+            end = end.max(snoozed_until.with_timezone(&at.timezone()));
+        }
+        if let Some(quiet_until) = self.config.quiet_until(end.naive_local()) {
+            // Quiet hours end at a wall-clock time, which a DST change in
+            // between moves by an hour
+            end = at.timezone()
+                .from_local_datetime(&quiet_until)
+                .earliest()
+                .unwrap_or(end + (quiet_until - end.naive_local()));
+        }
+        Some(end)
+    }
+
+    /// Get when the next reminder is due by the interval, or `None` once
+    /// today's reminders are used up
+    fn next_reminder_at(&self) -> Option<DateTime<Utc>> {
+        if self.day_state.reminders_sent >= self.config.max_reminders_per_day {
+            return None;
+        }
+
+        // The first reminder waits a full interval after the day starts or the
+        // service starts, whichever is later
+        let last_reminder = self.day_state.last_reminder_at.unwrap_or_else(|| {
+            let day_start = self.scheduler.day_start(self.day_state.day, &self.clock.now().timezone());
+            self.started_at.max(day_start.with_timezone(&Utc))
+        });
+        let interval = chrono::Duration::minutes(self.config.reminder_interval_minutes as i64);
+        Some(last_reminder + interval)
+    }
+
     fn should_send_reminder(&self) -> bool {
-        if self.reminders_sent_today >= self.config.max_reminders_per_day {
+        let Some(due) = self.next_reminder_at() else {
+            return false;
+        };
+
+        if self.reminders_paused() || self.clock.utc_now() < due {
             return false;
         }
 
         match self.db.get_today_summary() {
-            Ok((completed, _total)) => {
+            Ok(summary) => {
                 // Don't remind if goal is achieved
-                if is_daily_goal_achieved(completed) {
+                if summary.is_goal_achieved() {
                     return false;
                 }
-                
+
                 // Always remind unless goal is achieved
                 true
             }
@@ -45,163 +174,994 @@ impl ServiceState {
         }
     }
 
-    fn send_reminder(&mut self) -> Result<()> {
+    /// Get when the next reminder actually goes out, or `None` if none will
+    /// today: the goal is reached, reminders are used up or do-not-disturb is on
+    fn scheduled_reminder(&self) -> Option<DateTime<Zone>> {
+        let summary = self.db.get_today_summary().ok()?;
+        if summary.is_goal_achieved() {
+            return None;
+        }
+
+        let now = self.clock.now();
+        let due = self.next_reminder_at()?.with_timezone(&now.timezone());
+        self.pause_end(now.max(due))
+    }
+
+    fn reminder(&mut self) -> Result<Option<Notice>> {
         if !self.should_send_reminder() {
-            return Ok(());
-        }
-
-        let (completed, total) = self.db.get_today_summary()?;
-        
-        let (title, message) = if total == 0 {
-            (
-                "ThreeADay Reminder".to_string(),
-                "Time to add your first task for today! 🎯".to_string(),
-            )
-        } else {
-            let remaining = DAILY_GOAL_COMPLETION_COUNT.saturating_sub(completed);
-            (
-                "ThreeADay Reminder".to_string(),
-                format!(
-                    "You have {}/{} tasks completed. {} more to reach your daily goal! 💪",
-                    completed, total, remaining
-                ),
-            )
-        };
+            return Ok(None);
+        }
+        let notice = self.reminder_now()?;
+        self.day_state.reminders_sent += 1;
+        self.save_day_state()?;
+        Ok(Some(notice))
+    }
+
+    /// Get today's open tasks, to offer a "Done" button for
+    fn open_tasks(&self) -> Result<Vec<(i64, String)>> {
+        Ok(self.db.get_today_tasks()?
+            .into_iter()
+            .filter(|task| !task.completed)
+            .map(|task| (task.id, task.text))
+            .collect())
+    }
 
-        Notification::new()
-            .summary(&title)
-            .body(&message)
-            .timeout(5000)
-            .show()?;
+    /// Prepare a reminder and start the next interval from now. Only
+    /// scheduled reminders count towards `max_reminders_per_day`, so asking
+    /// for one doesn't use up those still to come today.
+    fn reminder_now(&mut self) -> Result<Notice> {
+        let summary = self.db.get_today_summary()?;
+        let open_tasks = self.open_tasks()?;
 
-        self.last_reminder_time = Some(Instant::now());
-        self.reminders_sent_today += 1;
+        self.day_state.last_reminder_at = Some(self.clock.utc_now());
+        self.save_day_state()?;
 
-        Ok(())
+        Ok(Notice::Reminder { summary, open_tasks })
     }
 
-    fn should_send_daily_reset(&self) -> bool {
-        // Check if it's time for daily reset (6 AM by default)
-        let now = chrono::Utc::now().time();
-        let reset_time = chrono::NaiveTime::parse_from_str(&self.config.daily_reset_time, "%H:%M")
-            .unwrap_or_else(|_| chrono::NaiveTime::from_hms_opt(6, 0, 0).unwrap());
-        
-        // Simple check: if it's past reset time and we haven't sent today's reset
-        if now.hour() >= reset_time.hour() && now.minute() >= reset_time.minute() {
-            if let Some(last_reset) = self.last_reset_time {
-                // Don't send another reset if we sent one in the last hour
-                return last_reset.elapsed().as_secs() > 3600;
+    fn daily_reset(&mut self) -> Result<Option<Notice>> {
+        // The morning greeting waits until quiet hours or a snooze are over
+        if self.reminders_paused() || self.scheduler.poll(&self.clock.now()).is_none() {
+            return Ok(None);
+        }
+
+        let goal = self.db.config().goal_for(self.db.today());
+        let streak = match self.db.get_streak() {
+            Ok(streak) => streak.current,
+            Err(e) => {
+                eprintln!("Error calculating streak: {}", e);
+                0
             }
-            return true;
+        };
+
+        let open_tasks = self.open_tasks()?;
+
+        self.day_state.reset_notified = true;
+        self.save_day_state()?;
+
+        Ok(Some(Notice::Reset { goal, streak, open_tasks }))
+    }
+
+    fn achievement(&mut self) -> Result<Option<Notice>> {
+        let summary = self.db.get_today_summary()?;
+
+        // A day can drop back below the goal when a task is un-checked; reminders
+        // resume then, but the celebration is only sent once per logical day
+        if !summary.is_goal_achieved() || self.day_state.achievement_notified {
+            return Ok(None);
+        }
+
+        self.day_state.achievement_notified = true;
+        self.save_day_state()?;
+
+        Ok(Some(Notice::Achievement { completed: summary.completed }))
+    }
+
+    /// Get the startup notice, unless the service already started earlier today
+    pub fn startup(&mut self) -> Result<Option<Notice>> {
+        // Only announce the first start of a day, not every restart
+        if self.day_state.startup_notified {
+            return Ok(None);
         }
-        
-        false
+
+        self.day_state.startup_notified = true;
+        self.save_day_state()?;
+        Ok(Some(Notice::Startup))
     }
 
-    fn send_daily_reset_notification(&mut self) -> Result<()> {
-        if !self.should_send_daily_reset() {
-            return Ok(());
+    /// Run one round of checks and return the notices that are due now
+    pub fn tick(&mut self) -> Vec<Notice> {
+        let mut notices = Vec::new();
+
+        if let Err(e) = self.refresh_day_state() {
+            eprintln!("Error loading service state: {}", e);
+        }
+
+        // Check for daily reset
+        match self.daily_reset() {
+            Ok(notice) => notices.extend(notice),
+            Err(e) => eprintln!("Error preparing daily reset notification: {}", e),
         }
 
-        let message = "🌅 Good morning! It's a fresh start. What 3 tasks will you complete today?";
-        
-        Notification::new()
-            .summary("ThreeADay - Fresh Start")
-            .body(message)
-            .timeout(8000)
-            .show()?;
+        // Check for reminders (every configured interval)
+        match self.reminder() {
+            Ok(notice) => notices.extend(notice),
+            Err(e) => eprintln!("Error preparing reminder: {}", e),
+        }
 
-        self.last_reset_time = Some(Instant::now());
-        self.reminders_sent_today = 0; // Reset reminder counter
+        // Check for achievement notification (when goal is reached)
+        match self.achievement() {
+            Ok(notice) => notices.extend(notice),
+            Err(e) => eprintln!("Error preparing achievement notification: {}", e),
+        }
+
+        notices
+    }
 
+    /// How long to sleep before the next tick: until the daily reset, a held
+    /// back greeting or the next reminder, whichever comes first
+    pub fn next_wakeup(&self) -> Duration {
+        let now = self.clock.now();
+        let mut wakeup = self.scheduler.next_reset(&now);
+
+        // The greeting goes out once quiet hours or a snooze are over
+        if self.scheduler.is_due(&now) {
+            wakeup = wakeup.min(self.pause_end(now).unwrap_or(wakeup));
+        }
+
+        // No reminders are scheduled once the goal is reached; a task that is
+        // un-checked wakes the loop through the database watcher
+        if let Some(reminder) = self.scheduled_reminder() {
+            wakeup = wakeup.min(reminder);
+        }
+
+        (wakeup - now).to_std().unwrap_or_default().clamp(MIN_SLEEP, MAX_SLEEP)
+    }
+
+    /// React to a button clicked on a notification
+    pub fn handle_action(&mut self, action: NoticeAction) -> Result<()> {
+        match action {
+            NoticeAction::Open => {
+                let mut gui = Command::new("threeaday-gui");
+                if let Some(path) = self.db.path() {
+                    gui.arg("--db").arg(path);
+                }
+                gui.spawn()?;
+            }
+            NoticeAction::Snooze => self.snooze(SNOOZE_MINUTES)?,
+            NoticeAction::Done(id) => {
+                self.db.set_source(EventSource::Notification);
+                let completed = self.db.complete_task(id);
+                self.db.set_source(EventSource::Service);
+                completed?;
+            }
+        }
+        Ok(())
+    }
+
+    fn snooze(&mut self, minutes: i64) -> Result<()> {
+        let snoozed_until = self.clock.utc_now() + chrono::Duration::minutes(minutes);
+        self.db.update_reminder_pause(|pause| pause.snoozed_until = Some(snoozed_until))?;
         Ok(())
     }
 
-    fn send_achievement_notification(&self) -> Result<()> {
-        let (completed, _total) = self.db.get_today_summary()?;
-        
-        if is_daily_goal_achieved(completed) {
-            let message = format!("🎉 Congratulations! You've completed {} tasks today. Goal achieved! 🎯", completed);
-            
-            Notification::new()
-                .summary("ThreeADay - Goal Achieved!")
-                .body(&message)
-                .timeout(8000)
-                .show()?;
+    /// Describe what the service is up to
+    pub fn status(&self) -> ServiceStatus {
+        ServiceStatus {
+            started_at: self.started_at,
+            day: self.day_state.day,
+            reminders_sent: self.day_state.reminders_sent,
+            max_reminders: self.config.max_reminders_per_day,
+            last_reminder_at: self.day_state.last_reminder_at,
+            next_reminder_at: self.scheduled_reminder().map(|at| at.with_timezone(&Utc)),
+            last_reset: self.scheduler.last_fired(),
+            paused: self.reminders_paused(),
+        }
+    }
+
+    /// Answer a request from the local socket
+    pub fn handle_request(&mut self, request: Request) -> Response {
+        let result = match request {
+            Request::GetStatus => return Response::Status(self.status()),
+            Request::Snooze { minutes } => self.snooze(minutes.map_or(SNOOZE_MINUTES, i64::from)),
+            Request::ReloadConfig => self.reload_config(),
+            Request::TriggerReminder => self.reminder_now().map(|notice| self.notify(&notice)),
+            // Subscriptions are handled by the connection itself
+            Request::Subscribe => Ok(()),
+        };
+
+        match result {
+            Ok(()) => Response::Ok,
+            Err(e) => Response::Error { message: e.to_string() },
         }
-        
+    }
+
+    /// Apply the config file again, keeping what was already sent today. A
+    /// file that doesn't parse or validate leaves the current config in place.
+    fn reload_config(&mut self) -> Result<()> {
+        let config = load_config(&self.config_path()?)?;
+
+        self.scheduler = ResetScheduler::new(config.reset_time(), self.scheduler.last_fired());
+        self.db.set_config(config.clone());
+        self.config = config;
         Ok(())
     }
 
-    async fn run_main_loop(&mut self) -> Result<()> {
-        let mut last_task_check = Instant::now();
-        let mut last_achievement_check: Option<Instant> = None;
-        
-        loop {
-            let now = Instant::now();
-            
-            // Check for daily reset
-            if let Err(e) = self.send_daily_reset_notification() {
-                eprintln!("Error sending daily reset notification: {}", e);
+    /// Reload the config after the file changed or on SIGHUP, and tell the
+    /// user if the new file can't be applied
+    pub fn reload_config_or_notify(&mut self) {
+        match self.reload_config() {
+            Ok(()) => println!("Config reloaded"),
+            Err(e) => {
+                eprintln!("Error reloading config: {:#}", e);
+                self.notify(&Notice::ConfigError { error: format!("{:#}", e) });
+            }
+        }
+    }
+
+    /// Apply the config file when the service starts. A file that can't be
+    /// applied is reported like on reload, and the service runs with the
+    /// current settings until the file is saved again.
+    pub fn load_config_or_notify(&mut self) {
+        if let Err(e) = self.reload_config() {
+            eprintln!("Error loading config, using the defaults: {:#}", e);
+            self.notify(&Notice::ConfigError { error: format!("{:#}", e) });
+        }
+    }
+
+    /// Get the channel signals for socket subscribers are sent on
+    pub fn signals(&self) -> broadcast::Sender<Signal> {
+        self.signals.clone()
+    }
+
+    /// Tell subscribers about tasks completed since the last round and a
+    /// reached goal
+    fn emit_signals(&mut self, notices: &[Notice]) {
+        if let Ok(summary) = self.db.get_today_summary() {
+            let previous = self.completed.replace((summary.day, summary.completed));
+            if previous.is_some_and(|(day, completed)| day == summary.day && completed < summary.completed) {
+                let _ = self.signals.send(Signal::TaskCompleted { completed: summary.completed, total: summary.total });
+            }
+        }
+
+        for notice in notices {
+            if let Notice::Achievement { completed } = notice {
+                let _ = self.signals.send(Signal::GoalAchieved { completed: *completed });
             }
-            
-            // Check for reminders (every configured interval)
-            if let Some(last_reminder) = self.last_reminder_time {
-                if last_reminder.elapsed().as_secs() >= self.config.reminder_interval_minutes * 60 {
-                    if let Err(e) = self.send_reminder() {
-                        eprintln!("Error sending reminder: {}", e);
+        }
+    }
+
+    fn notify(&self, notice: &Notice) {
+        if let Err(e) = self.notifier.notify(notice) {
+            eprintln!("Error showing notification: {}", e);
+        }
+    }
+
+    /// Send the startup notification, unless already sent today
+    pub fn announce_startup(&mut self) {
+        match self.startup() {
+            Ok(Some(notice)) => self.notify(&notice),
+            Ok(None) => {}
+            Err(e) => eprintln!("Error sending startup notification: {}", e),
+        }
+    }
+
+    /// Run one round of checks and send whatever is due
+    pub fn run_once(&mut self) {
+        let notices = self.tick();
+        for notice in &notices {
+            self.notify(notice);
+        }
+        self.emit_signals(&notices);
+    }
+
+    async fn run_main_loop(&mut self, mut actions: UnboundedReceiver<NoticeAction>) -> Result<()> {
+        // Requests from the local socket are answered between rounds; the
+        // service works without it, just not controllable from the CLI
+        let (request_tx, mut requests) = mpsc::unbounded_channel::<IpcRequest>();
+        let socket = socket_path().and_then(|path| ipc::listen(&path, request_tx, self.signals()).map(|_| path));
+        match &socket {
+            Ok(path) => println!("Listening on {}", path.display()),
+            Err(e) => eprintln!("Error opening the service socket: {}", e),
+        }
+
+        // Tasks changed from the CLI or GUI are picked up right away; without
+        // a watcher the loop still wakes up on schedule
+        let (change_tx, mut changes) = mpsc::unbounded_channel();
+        let _watcher = self.db.path().and_then(|path| {
+            watch_database(path, move || {
+                let _ = change_tx.send(());
+            })
+            .inspect_err(|e| eprintln!("Error watching the database, changes are noticed late: {}", e))
+            .ok()
+        });
+
+        // Edits to the config file apply right away, as does SIGHUP
+        let (config_tx, mut config_changes) = mpsc::unbounded_channel();
+        let _config_watcher = self.config_path()
+            .and_then(|path| {
+                watch_config(&path, move || {
+                    let _ = config_tx.send(());
+                })
+            })
+            .inspect_err(|e| eprintln!("Error watching the config file, reload it with SIGHUP: {}", e))
+            .ok();
+        let mut hangups = signal(SignalKind::hangup())?;
+
+        self.announce_startup();
+
+        loop {
+            self.run_once();
+
+            // Sleep until the next check, but handle clicked buttons and
+            // database changes right away; the next round then picks up e.g.
+            // a reached goal
+            tokio::select! {
+                _ = sleep(self.next_wakeup()) => {}
+                Some(action) = actions.recv() => {
+                    if let Err(e) = self.handle_action(action) {
+                        eprintln!("Error handling notification action: {}", e);
                     }
                 }
-            } else {
-                // Send first reminder
-                if let Err(e) = self.send_reminder() {
-                    eprintln!("Error sending reminder: {}", e);
+                Some(()) = changes.recv() => {
+                    // A single write touches several files, so handle the
+                    // whole burst in one round
+                    while changes.try_recv().is_ok() {}
                 }
-            }
-            
-            // Check for achievement notification (when goal is reached)
-            if last_task_check.elapsed().as_secs() >= 30 {
-                match self.db.get_today_summary() {
-                    Ok((completed, _total)) => {
-                        if is_daily_goal_achieved(completed) {
-                            // Only send achievement notification once per day
-                            if last_achievement_check.is_none() 
-                                || last_achievement_check.unwrap().elapsed().as_secs() > 86400 {
-                                if let Err(e) = self.send_achievement_notification() {
-                                    eprintln!("Error sending achievement notification: {}", e);
-                                }
-                                last_achievement_check = Some(now);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Error checking task summary: {}", e);
-                    }
+                Some((request, reply)) = requests.recv() => {
+                    let _ = reply.send(self.handle_request(request));
                 }
-                last_task_check = now;
+                Some(()) = config_changes.recv() => {
+                    // Let the editor finish writing before reading the file
+                    sleep(CONFIG_SETTLE).await;
+                    while config_changes.try_recv().is_ok() {}
+                    self.reload_config_or_notify();
+                }
+                Some(()) = hangups.recv() => self.reload_config_or_notify(),
             }
-            
-            // Sleep for a short interval
-            sleep(Duration::from_secs(60)).await;
         }
     }
 }
 
-pub async fn run_service() -> Result<()> {
+/// Load the config file, logging settings that aren't recognised
+fn load_config(path: &Path) -> Result<Config> {
+    let (config, unknown_keys) = Config::load_with_warnings_from(path)?;
+    for key in unknown_keys {
+        eprintln!("Warning: config.toml {}", key);
+    }
+    Ok(config)
+}
+
+/// Run the service on the database at `db_path`, or the default one.
+/// Headless, or without a session bus to show desktop notifications on,
+/// notifications are printed to stdout instead.
+pub async fn run_service(headless: bool, db_path: Option<PathBuf>) -> Result<()> {
     println!("Starting ThreeADay service...");
-    
-    let mut state = ServiceState::new()?;
-    
-    // Send startup notification
-    if let Err(e) = Notification::new()
-        .summary("ThreeADay Service")
-        .body("Service started - reminders and daily resets are now active! 🚀")
-        .timeout(3000)
-        .show()
-    {
-        eprintln!("Error sending startup notification: {}", e);
-    }
-    
+
+    let (action_tx, action_rx) = mpsc::unbounded_channel();
+    let notifier: Box<dyn Notifier> = if headless {
+        Box::new(LogNotifier)
+    } else if std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_none() {
+        println!("No D-Bus session found, printing notifications to stdout");
+        Box::new(LogNotifier)
+    } else {
+        Box::new(DesktopNotifier::new(action_tx))
+    };
+
+    let db_path = match db_path {
+        Some(path) => path,
+        None => Database::default_path()?,
+    };
+    let db = Database::open(&db_path)?;
+    let config_path = Config::get_config_path()?;
+    let mut state = ServiceState::new(db, Config::default(), notifier)?.with_config_path(config_path);
+    state.load_config_or_notify();
+
     // Run main service loop
-    state.run_main_loop().await
-}
\ No newline at end of file
+    state.run_main_loop(action_rx).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifier::RecordingNotifier;
+    use chrono_tz::America::New_York;
+    use chrono_tz::Tz;
+    use tempfile::TempDir;
+    use threeaday_core::{DaySummary, FakeClock, QuietHours};
+
+    struct TestEnv {
+        tz: Tz,
+        clock: Arc<FakeClock>,
+        notifier: RecordingNotifier,
+        temp_dir: TempDir,
+    }
+
+    impl TestEnv {
+        fn at(now: &str) -> Self {
+            Self::in_zone(Tz::UTC, now)
+        }
+
+        /// Start at a local time in a real timezone, with its DST changes
+        fn in_zone(tz: Tz, now: &str) -> Self {
+            TestEnv {
+                tz,
+                clock: Arc::new(FakeClock::in_zone(tz, now)),
+                notifier: RecordingNotifier::default(),
+                temp_dir: TempDir::new().unwrap(),
+            }
+        }
+
+        /// Open another handle on the service's database
+        fn db(&self) -> Database {
+            Database::open(self.temp_dir.path().join("tasks.db")).unwrap().with_clock(self.clock.clone())
+        }
+
+        /// The config file the service reloads
+        fn config_path(&self) -> PathBuf {
+            self.temp_dir.path().join("config").join("config.toml")
+        }
+
+        fn service(&self) -> ServiceState {
+            self.service_with(Config::default())
+        }
+
+        fn service_with(&self, config: Config) -> ServiceState {
+            ServiceState::new(self.db(), config, Box::new(self.notifier.clone()))
+                .unwrap()
+                .with_config_path(self.config_path())
+        }
+
+        /// Run the service the way the main loop does until the clock reaches
+        /// `until`, and return the notifications it sent
+        fn run_until(&self, service: &mut ServiceState, until: &str) -> Vec<Notice> {
+            let until = FakeClock::in_zone(self.tz, until).now();
+            while self.clock.now() < until {
+                service.run_once();
+                let wakeup = chrono::Duration::from_std(service.next_wakeup()).unwrap();
+                self.clock.advance(wakeup.min(until - self.clock.now()));
+            }
+            self.notifier.take()
+        }
+    }
+
+    fn reminders(notices: &[Notice]) -> usize {
+        notices.iter().filter(|notice| matches!(notice, Notice::Reminder { .. })).count()
+    }
+
+    fn resets(notices: &[Notice]) -> usize {
+        notices.iter().filter(|notice| matches!(notice, Notice::Reset { .. })).count()
+    }
+
+    fn day(s: &str) -> chrono::NaiveDate {
+        chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn hours(start: &str, end: &str) -> QuietHours {
+        let time = |s| chrono::NaiveTime::parse_from_str(s, "%H:%M").unwrap();
+        QuietHours::new(time(start), time(end))
+    }
+
+    fn reminder(completed: usize, total: usize, open_tasks: &[(i64, &str)]) -> Notice {
+        Notice::Reminder {
+            summary: DaySummary { day: day("2026-10-16"), completed, total, goal: 3 },
+            open_tasks: open_tasks.iter().map(|&(id, text)| (id, text.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_simulated_day_without_tasks() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+
+        service.announce_startup();
+        let notices = env.run_until(&mut service, "2026-10-17 05:59");
+
+        // Reminders are capped at max_reminders_per_day
+        let mut expected = vec![Notice::Startup, Notice::Reset { goal: 3, streak: 0, open_tasks: Vec::new() }];
+        expected.extend(std::iter::repeat_n(reminder(0, 0, &[]), 8));
+        assert_eq!(notices, expected);
+    }
+
+    #[test]
+    fn test_simulated_day_with_tasks() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut db = env.db();
+
+        service.announce_startup();
+        let mut notices = env.run_until(&mut service, "2026-10-16 07:10");
+        let walk = db.add_task("Walk").unwrap();
+        let dishes = db.add_task("Dishes").unwrap();
+        let call = db.add_task("Call a friend").unwrap();
+
+        notices.extend(env.run_until(&mut service, "2026-10-16 08:00"));
+        db.complete_task(walk).unwrap();
+        db.complete_task(dishes).unwrap();
+
+        notices.extend(env.run_until(&mut service, "2026-10-16 09:00"));
+        db.complete_task(call).unwrap();
+
+        notices.extend(env.run_until(&mut service, "2026-10-17 05:59"));
+        assert_eq!(notices, vec![
+            Notice::Startup,
+            Notice::Reset { goal: 3, streak: 0, open_tasks: Vec::new() },
+            reminder(0, 3, &[(walk, "Walk"), (dishes, "Dishes"), (call, "Call a friend")]),
+            reminder(2, 3, &[(call, "Call a friend")]),
+            Notice::Achievement { completed: 3 },
+        ]);
+    }
+
+    #[test]
+    fn test_simulated_night_across_dst_change() {
+        // Clocks in New York go back from 02:00 to 01:00 on 2026-11-01
+        let env = TestEnv::in_zone(New_York, "2026-10-31 23:00");
+        let config = Config { quiet_hours: vec![hours("22:00", "06:00")], ..Config::default() };
+        let mut service = env.service_with(config);
+
+        let next_reset = service.scheduler.next_reset(&env.clock.now());
+        assert_eq!(next_reset.with_timezone(&Utc), FakeClock::at("2026-11-01 11:00").utc_now());
+
+        assert!(env.run_until(&mut service, "2026-11-01 05:59").is_empty());
+        let notices = env.run_until(&mut service, "2026-11-01 06:01");
+        assert_eq!(notices, vec![Notice::Reset { goal: 3, streak: 0, open_tasks: Vec::new() }]);
+
+        // The first reminder comes a full interval after the 06:00 reset
+        let notices = env.run_until(&mut service, "2026-11-01 07:00");
+        assert_eq!(reminders(&notices), 1);
+        let first = FakeClock::in_zone(New_York, "2026-11-01 06:45").utc_now();
+        assert_eq!(service.day_state.last_reminder_at, Some(first));
+    }
+
+    #[test]
+    fn test_reminders_respect_interval() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+
+        let notices = env.run_until(&mut service, "2026-10-16 08:31");
+
+        // Not right at startup, then every 45 minutes: 07:45 and 08:30
+        assert_eq!(reminders(&notices), 2);
+        let last = service.day_state.last_reminder_at.unwrap();
+        assert_eq!(last.format("%H:%M").to_string(), "08:30");
+    }
+
+    #[test]
+    fn test_achievement_stops_reminders() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut db = env.db();
+
+        let notices = env.run_until(&mut service, "2026-10-16 08:00");
+        assert_eq!(reminders(&notices), 1);
+
+        for text in ["Walk", "Dishes", "Call a friend"] {
+            let id = db.add_task(text).unwrap();
+            db.complete_task(id).unwrap();
+        }
+        let notices = env.run_until(&mut service, "2026-10-16 22:00");
+
+        assert_eq!(notices, vec![Notice::Achievement { completed: 3 }]);
+    }
+
+    #[test]
+    fn test_achievement_notified_once_per_day() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut db = env.db();
+
+        let ids: Vec<i64> = ["One", "Two", "Three"].iter().map(|text| db.add_task(text).unwrap()).collect();
+        for &id in &ids {
+            db.complete_task(id).unwrap();
+        }
+        env.run_until(&mut service, "2026-10-16 09:00");
+
+        // Dropping below the goal resumes reminders, but reaching it again
+        // doesn't celebrate twice
+        db.uncomplete_task(ids[2]).unwrap();
+        let notices = env.run_until(&mut service, "2026-10-16 09:30");
+        assert_eq!(reminders(&notices), 1);
+
+        db.complete_task(ids[2]).unwrap();
+        let notices = env.run_until(&mut service, "2026-10-16 12:00");
+        assert!(notices.is_empty());
+    }
+
+    #[test]
+    fn test_restart_does_not_repeat_notices() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+
+        service.announce_startup();
+        env.run_until(&mut service, "2026-10-16 12:00");
+        let sent = service.day_state.reminders_sent as usize;
+
+        let mut restarted = env.service();
+        restarted.announce_startup();
+        let notices = env.run_until(&mut restarted, "2026-10-17 05:59");
+
+        assert!(!notices.contains(&Notice::Startup));
+        assert_eq!(resets(&notices), 0);
+        assert_eq!(reminders(&notices), 8 - sent);
+    }
+
+    #[test]
+    fn test_reset_offers_tasks_moved_to_the_day() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut db = env.db();
+        env.run_until(&mut service, "2026-10-16 08:00");
+
+        let id = db.add_task("Call the dentist").unwrap();
+        db.reschedule_task(id, day("2026-10-17")).unwrap();
+        env.run_until(&mut service, "2026-10-17 05:00");
+        let notices = env.run_until(&mut service, "2026-10-17 06:01");
+
+        let open_tasks = vec![(id, "Call the dentist".to_string())];
+        assert_eq!(notices, vec![Notice::Reset { goal: 3, streak: 0, open_tasks }]);
+        service.handle_action(NoticeAction::Done(id)).unwrap();
+        assert!(db.get_task(id).unwrap().unwrap().completed);
+    }
+
+    #[test]
+    fn test_new_day_resets_counters() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+
+        env.run_until(&mut service, "2026-10-17 05:00");
+        assert_eq!(service.day_state.reminders_sent, 8);
+
+        let notices = env.run_until(&mut service, "2026-10-17 06:01");
+
+        assert_eq!(notices, vec![Notice::Reset { goal: 3, streak: 0, open_tasks: Vec::new() }]);
+        assert_eq!(service.day_state.reminders_sent, 0);
+        assert_eq!(service.day_state.day.to_string(), "2026-10-17");
+
+        // The first reminder of the new day comes one interval after the reset
+        let notices = env.run_until(&mut service, "2026-10-17 06:46");
+        assert_eq!(reminders(&notices), 1);
+        assert_eq!(service.day_state.reminders_sent, 1);
+    }
+
+    #[test]
+    fn test_quiet_hours_hold_back_reminders() {
+        let env = TestEnv::at("2026-10-16 20:00");
+        let config = Config {
+            quiet_hours: vec![hours("22:00", "08:00")],
+            ..Config::default()
+        };
+        let mut service = env.service_with(config);
+
+        // 20:45 and 21:30, then nothing until quiet hours end
+        let notices = env.run_until(&mut service, "2026-10-17 07:59");
+        assert_eq!(reminders(&notices), 2);
+        assert_eq!(resets(&notices), 1);
+
+        // The morning greeting was held back too and comes at 08:00
+        let notices = env.run_until(&mut service, "2026-10-17 08:01");
+        assert_eq!(notices[0], Notice::Reset { goal: 3, streak: 0, open_tasks: Vec::new() });
+        assert!(matches!(notices[1], Notice::Reminder { .. }));
+    }
+
+    #[test]
+    fn test_snooze_holds_back_reminders() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut db = env.db();
+
+        env.run_until(&mut service, "2026-10-16 08:00");
+        let snoozed_until = env.clock.utc_now() + chrono::Duration::hours(2);
+        db.save_reminder_pause(&ReminderPause { snoozed_until: Some(snoozed_until), dnd: false }).unwrap();
+
+        let notices = env.run_until(&mut service, "2026-10-16 09:59");
+        assert!(notices.is_empty());
+
+        let notices = env.run_until(&mut service, "2026-10-16 10:01");
+        assert_eq!(reminders(&notices), 1);
+    }
+
+    #[test]
+    fn test_dnd_holds_back_reminders_until_off() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut db = env.db();
+
+        db.save_reminder_pause(&ReminderPause { snoozed_until: None, dnd: true }).unwrap();
+        let notices = env.run_until(&mut service, "2026-10-16 18:00");
+        assert!(notices.is_empty());
+
+        db.save_reminder_pause(&ReminderPause::default()).unwrap();
+        let notices = env.run_until(&mut service, "2026-10-16 18:01");
+        assert_eq!(notices[0], Notice::Reset { goal: 3, streak: 0, open_tasks: Vec::new() });
+        assert!(matches!(notices[1], Notice::Reminder { .. }));
+    }
+
+    #[test]
+    fn test_start_before_reset_greets_once() {
+        let env = TestEnv::at("2026-10-16 05:00");
+        let mut service = env.service();
+
+        // The 15th is nearly over, so only the 16th is greeted, at 06:00
+        assert_eq!(resets(&env.run_until(&mut service, "2026-10-16 05:59")), 0);
+        assert_eq!(resets(&env.run_until(&mut service, "2026-10-16 12:00")), 1);
+        assert_eq!(service.status().last_reset, Some(day("2026-10-16")));
+    }
+
+    #[test]
+    fn test_wakes_up_at_reset_time() {
+        let env = TestEnv::at("2026-10-17 05:59");
+        let mut service = env.service();
+
+        service.run_once();
+        env.clock.advance(chrono::Duration::seconds(20));
+        assert_eq!(service.next_wakeup(), Duration::from_secs(40));
+    }
+
+    #[test]
+    fn test_wakes_up_for_next_reminder() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service_with(Config { reminder_interval_minutes: 10, ..Config::default() });
+
+        service.run_once();
+        assert_eq!(service.next_wakeup(), Duration::from_secs(10 * 60));
+
+        // Nothing is due for a while, but the loop still checks the clock
+        let mut service = env.service();
+        service.run_once();
+        assert_eq!(service.next_wakeup(), MAX_SLEEP);
+    }
+
+    #[test]
+    fn test_wakes_up_when_snooze_ends() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service_with(Config { reminder_interval_minutes: 10, ..Config::default() });
+
+        service.run_once();
+        let snoozed_until = env.clock.utc_now() + chrono::Duration::minutes(12);
+        env.db().save_reminder_pause(&ReminderPause { snoozed_until: Some(snoozed_until), dnd: false }).unwrap();
+
+        assert_eq!(service.next_wakeup(), Duration::from_secs(12 * 60));
+    }
+
+    #[test]
+    fn test_wakes_up_when_quiet_hours_end() {
+        let env = TestEnv::at("2026-10-16 21:55");
+        let config = Config {
+            reminder_interval_minutes: 10,
+            quiet_hours: vec![hours("22:00", "22:07")],
+            ..Config::default()
+        };
+        let mut service = env.service_with(config);
+
+        service.run_once();
+        assert_eq!(service.next_wakeup(), Duration::from_secs(12 * 60));
+    }
+
+    #[test]
+    fn test_no_reminder_wakeups_after_goal() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service_with(Config { reminder_interval_minutes: 10, ..Config::default() });
+        let mut db = env.db();
+
+        for text in ["Walk", "Dishes", "Call a friend"] {
+            let id = db.add_task(text).unwrap();
+            db.complete_task(id).unwrap();
+        }
+        service.run_once();
+
+        assert_eq!(service.next_wakeup(), MAX_SLEEP);
+    }
+
+    #[test]
+    fn test_reminder_offers_open_tasks() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut db = env.db();
+
+        let walk = db.add_task("Walk").unwrap();
+        let dishes = db.add_task("Dishes").unwrap();
+        db.complete_task(walk).unwrap();
+
+        let notices = env.run_until(&mut service, "2026-10-16 07:46");
+        let Some(Notice::Reminder { summary, open_tasks }) = notices.last() else {
+            panic!("Expected a reminder, got {:?}", notices);
+        };
+        assert_eq!((summary.completed, summary.total), (1, 2));
+        assert_eq!(open_tasks, &vec![(dishes, "Dishes".to_string())]);
+    }
+
+    #[test]
+    fn test_done_action_completes_task() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut db = env.db();
+
+        let ids: Vec<i64> = ["One", "Two", "Three"].iter().map(|text| db.add_task(text).unwrap()).collect();
+        db.complete_task(ids[0]).unwrap();
+        db.complete_task(ids[1]).unwrap();
+        env.run_until(&mut service, "2026-10-16 08:00");
+
+        service.handle_action(NoticeAction::Done(ids[2])).unwrap();
+
+        assert!(db.get_task(ids[2]).unwrap().unwrap().completed);
+        let day = db.today();
+        let last = db.get_events_between(day, day).unwrap().pop().unwrap();
+        assert_eq!((last.task_id, last.source), (ids[2], EventSource::Notification));
+        service.run_once();
+        assert_eq!(env.notifier.take(), vec![Notice::Achievement { completed: 3 }]);
+    }
+
+    #[test]
+    fn test_snooze_action_pauses_reminders() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+
+        let notices = env.run_until(&mut service, "2026-10-16 08:29");
+        assert_eq!(reminders(&notices), 1);
+
+        // The reminder due at 08:30 waits until the snooze ends at 08:59
+        service.handle_action(NoticeAction::Snooze).unwrap();
+        let pause = env.db().get_reminder_pause().unwrap();
+        assert_eq!(pause.snoozed_until, Some(env.clock.utc_now() + chrono::Duration::minutes(30)));
+
+        let notices = env.run_until(&mut service, "2026-10-16 08:59");
+        assert!(notices.is_empty());
+        let notices = env.run_until(&mut service, "2026-10-16 09:00");
+        assert_eq!(reminders(&notices), 1);
+    }
+
+    #[test]
+    fn test_status() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+
+        env.run_until(&mut service, "2026-10-16 08:00");
+        let status = service.status();
+
+        assert_eq!(status.day, day("2026-10-16"));
+        assert_eq!((status.reminders_sent, status.max_reminders), (1, 8));
+        assert_eq!(status.last_reminder_at, Some(FakeClock::at("2026-10-16 07:45").utc_now()));
+        assert_eq!(status.next_reminder_at, Some(FakeClock::at("2026-10-16 08:30").utc_now()));
+        assert_eq!(status.last_reset, Some(day("2026-10-16")));
+        assert!(!status.paused);
+    }
+
+    #[test]
+    fn test_snooze_request_delays_next_reminder() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+
+        env.run_until(&mut service, "2026-10-16 08:00");
+        assert_eq!(service.handle_request(Request::Snooze { minutes: Some(60) }), Response::Ok);
+
+        let status = service.status();
+        assert!(status.paused);
+        assert_eq!(status.next_reminder_at, Some(FakeClock::at("2026-10-16 09:00").utc_now()));
+    }
+
+    #[test]
+    fn test_trigger_reminder_request() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+
+        assert_eq!(service.handle_request(Request::TriggerReminder), Response::Ok);
+
+        assert_eq!(env.notifier.take(), vec![reminder(0, 0, &[])]);
+        assert_eq!(service.status().next_reminder_at, Some(FakeClock::at("2026-10-16 07:45").utc_now()));
+    }
+
+    #[test]
+    fn test_triggered_reminders_dont_use_up_the_daily_cap() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service_with(Config { max_reminders_per_day: 2, ..Config::default() });
+        env.run_until(&mut service, "2026-10-16 07:01");
+
+        for _ in 0..3 {
+            assert_eq!(service.handle_request(Request::TriggerReminder), Response::Ok);
+        }
+        assert_eq!(service.status().reminders_sent, 0);
+
+        // Both scheduled reminders still go out
+        let notices = env.run_until(&mut service, "2026-10-16 12:00");
+        assert_eq!(reminders(&notices), 3 + 2);
+    }
+
+    #[test]
+    fn test_reload_config_request_keeps_day_state() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        env.run_until(&mut service, "2026-10-16 08:00");
+
+        let config = Config { max_reminders_per_day: 2, ..Config::default() };
+        config.save_to(&env.config_path()).unwrap();
+        assert_eq!(service.handle_request(Request::ReloadConfig), Response::Ok);
+
+        let status = service.status();
+        assert_eq!((status.reminders_sent, status.max_reminders), (1, 2));
+        assert_eq!(status.last_reset, Some(day("2026-10-16")));
+    }
+
+    #[test]
+    fn test_config_reload_applies_new_settings() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut db = env.db();
+        env.run_until(&mut service, "2026-10-16 08:00");
+
+        let config = Config { daily_goal: 1, reminder_interval_minutes: 10, ..Config::default() };
+        config.save_to(&env.config_path()).unwrap();
+        service.reload_config_or_notify();
+
+        // Today's reminders so far still count
+        assert!(env.notifier.take().is_empty());
+        assert_eq!(service.day_state.reminders_sent, 1);
+        assert_eq!(service.status().next_reminder_at, Some(FakeClock::at("2026-10-16 08:00").utc_now()));
+
+        // The new goal applies to today
+        let id = db.add_task("Walk").unwrap();
+        db.complete_task(id).unwrap();
+        service.run_once();
+        assert_eq!(env.notifier.take(), vec![Notice::Achievement { completed: 1 }]);
+    }
+
+    #[test]
+    fn test_invalid_config_keeps_previous_settings() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+
+        let config_path = env.config_path();
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        std::fs::write(&config_path, "reminder_interval_minutes = \"often\"").unwrap();
+        service.reload_config_or_notify();
+
+        let notices = env.notifier.take();
+        assert!(matches!(&notices[..], [Notice::ConfigError { error }] if error.contains("Failed to parse config")));
+        assert_eq!(service.config.reminder_interval_minutes, 45);
+
+        let config = Config { reminder_interval_minutes: 0, ..Config::default() };
+        config.save_to(&env.config_path()).unwrap();
+        assert!(matches!(service.handle_request(Request::ReloadConfig), Response::Error { .. }));
+        assert_eq!(service.config.reminder_interval_minutes, 45);
+    }
+
+    #[test]
+    fn test_invalid_config_at_startup_uses_defaults() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let config_path = env.config_path();
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        std::fs::write(&config_path, "daily_goal = 0\n").unwrap();
+
+        let mut service = env.service();
+        service.load_config_or_notify();
+        let notices = env.notifier.take();
+        assert!(matches!(&notices[..], [Notice::ConfigError { error }] if error.contains("daily_goal")));
+        assert_eq!(service.config.daily_goal, 3);
+
+        // The next valid save is picked up
+        Config { daily_goal: 2, ..Config::default() }.save_to(&config_path).unwrap();
+        service.reload_config_or_notify();
+        assert!(env.notifier.take().is_empty());
+        assert_eq!(service.config.daily_goal, 2);
+    }
+
+    #[test]
+    fn test_signals() {
+        let env = TestEnv::at("2026-10-16 07:00");
+        let mut service = env.service();
+        let mut signals = service.signals().subscribe();
+        let mut db = env.db();
+
+        let ids: Vec<i64> = ["One", "Two", "Three"].iter().map(|text| db.add_task(text).unwrap()).collect();
+        service.run_once();
+        db.complete_task(ids[0]).unwrap();
+        db.complete_task(ids[1]).unwrap();
+        service.run_once();
+        db.complete_task(ids[2]).unwrap();
+        service.run_once();
+
+        assert_eq!(signals.try_recv().unwrap(), Signal::TaskCompleted { completed: 2, total: 3 });
+        assert_eq!(signals.try_recv().unwrap(), Signal::TaskCompleted { completed: 3, total: 3 });
+        assert_eq!(signals.try_recv().unwrap(), Signal::GoalAchieved { completed: 3 });
+        assert!(signals.try_recv().is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::ffi::OsStr;
+use std::path::Path;
+use threeaday_core::{Context, Result};
+
+/// Call `on_change` whenever the CLI or GUI writes to the database at `path`.
+///
+/// SQLite also writes to side files next to the database (`-journal`,
+/// `-wal`), so the whole directory is watched and events are filtered by
+/// name. Watching stops when the returned watcher is dropped.
+pub fn watch_database(path: &Path, on_change: impl Fn() + Send + 'static) -> Result<RecommendedWatcher> {
+    let name = path.file_name().context("Database path has no file name")?.to_os_string();
+    watch_dir(path, move |event| is_database_change(event, &name), on_change)
+}
+
+/// Call `on_change` whenever the config file at `path` is written, replaced
+/// or removed.
+///
+/// Editors often save by writing a new file and renaming it over the old
+/// one, so the directory is watched rather than the file itself.
+pub fn watch_config(path: &Path, on_change: impl Fn() + Send + 'static) -> Result<RecommendedWatcher> {
+    let name = path.file_name().context("Config path has no file name")?.to_os_string();
+    watch_dir(path, move |event| is_write(event) && touches(event, |file| file == name), on_change)
+}
+
+/// Watch the directory `path` is in for events that pass `filter`
+fn watch_dir(
+    path: &Path,
+    filter: impl Fn(&Event) -> bool + Send + 'static,
+    on_change: impl Fn() + Send + 'static,
+) -> Result<RecommendedWatcher> {
+    let dir = path.parent().context("Watched path has no directory")?;
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        if let Ok(event) = event {
+            if filter(&event) {
+                on_change();
+            }
+        }
+    })?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", dir.display()))?;
+    Ok(watcher)
+}
+
+/// Check whether an event writes to the database or one of its side files
+fn is_database_change(event: &Event, db_name: &OsStr) -> bool {
+    is_write(event) && touches(event, |name| name.as_encoded_bytes().starts_with(db_name.as_encoded_bytes()))
+}
+
+fn is_write(event: &Event) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_))
+}
+
+/// Check whether any file the event is about has a matching name
+fn touches(event: &Event, matches: impl Fn(&OsStr) -> bool) -> bool {
+    event.paths.iter().any(|path| path.file_name().is_some_and(&matches))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, ModifyKind};
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(path.into())
+    }
+
+    #[test]
+    fn test_is_database_change() {
+        let name = OsStr::new("tasks.db");
+        let modify = EventKind::Modify(ModifyKind::Any);
+
+        assert!(is_database_change(&event(modify, "/data/tasks.db"), name));
+        assert!(is_database_change(&event(modify, "/data/tasks.db-wal"), name));
+        assert!(!is_database_change(&event(modify, "/data/notes.txt"), name));
+        assert!(!is_database_change(&event(EventKind::Access(AccessKind::Any), "/data/tasks.db"), name));
+    }
+
+    #[test]
+    fn test_watch_database_sees_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("tasks.db");
+        std::fs::write(&db_path, "").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let _watcher = watch_database(&db_path, move || {
+            let _ = tx.send(());
+        })
+        .unwrap();
+
+        std::fs::write(temp_dir.path().join("tasks.db-journal"), "changed").unwrap();
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn test_watch_config_sees_replaced_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(&config_path, "daily_goal = 3").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let _watcher = watch_config(&config_path, move || {
+            let _ = tx.send(());
+        })
+        .unwrap();
+
+        // Saved the way many editors do: write elsewhere, then rename
+        let saved = temp_dir.path().join(".config.toml.swp");
+        std::fs::write(&saved, "daily_goal = 1").unwrap();
+        std::fs::rename(&saved, &config_path).unwrap();
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+}